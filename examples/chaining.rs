@@ -111,7 +111,7 @@ fn main() {
         glyph("box.heavy.tl").unwrap(),
         glyph("box.heavy.h").unwrap().repeat(20),
         glyph("box.heavy.tr").unwrap(),
-        style("DASHBOARD", Style::SmallCaps),
+        style("DASHBOARD", Style::PetiteCaps),
         glyph("box.heavy.tl").unwrap(),
         glyph("box.heavy.h").unwrap().repeat(20),
         glyph("box.heavy.tr").unwrap()
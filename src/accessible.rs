@@ -0,0 +1,435 @@
+use crate::charmap::CharMap;
+use crate::glyphs::{glyph, GLYPH_NAMES};
+use crate::grapheme::graphemes;
+use crate::styles::{style, Style};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+use std::sync::OnceLock;
+
+/// Styled text paired with a plain alternative for screen readers
+///
+/// Screen readers announce styled characters by name ("mathematical bold
+/// capital H"), so anything read aloud should use [`Accessible::alt`].
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{Accessible, Style};
+///
+/// let hello = Accessible::style("Hello", Style::MathBold);
+/// assert_eq!(hello.to_string(), "𝐇𝐞𝐥𝐥𝐨");
+/// assert_eq!(hello.alt, "Hello");
+/// assert_eq!(hello.to_html(), r#"<span aria-label="Hello">𝐇𝐞𝐥𝐥𝐨</span>"#);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Accessible {
+    /// The styled text, for display
+    pub text: String,
+    /// Plain text with the same meaning, for screen readers
+    pub alt: String,
+}
+
+impl Accessible {
+    /// Pair styled text with its alternative
+    pub fn new(text: impl Into<String>, alt: impl Into<String>) -> Self {
+        Accessible {
+            text: text.into(),
+            alt: alt.into(),
+        }
+    }
+
+    /// Style `text`, keeping the original as the alternative
+    pub fn style<M: CharMap>(text: &str, map: M) -> Self {
+        Accessible::new(style(text, map), text)
+    }
+
+    /// Wrap already styled text, recovering the alternative with [`unstyle`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use prettychars::Accessible;
+    ///
+    /// assert_eq!(Accessible::from_styled("𝐁𝐢𝐠 ⓝⓔⓦⓢ").alt, "Big news");
+    /// ```
+    pub fn from_styled(text: &str) -> Self {
+        Accessible::new(text, unstyle(text))
+    }
+
+    /// An HTML `<span>` showing the styled text with the alternative as its
+    /// `aria-label`
+    ///
+    /// Both parts are HTML-escaped.
+    pub fn to_html(&self) -> String {
+        format!(
+            "<span aria-label=\"{}\">{}</span>",
+            escape_html(&self.alt),
+            escape_html(&self.text)
+        )
+    }
+}
+
+impl fmt::Display for Accessible {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Where a styled character comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Origin {
+    /// Mathematical Alphanumeric Symbols and their Letterlike Symbols fill-ins
+    Math,
+    /// Enclosed, small capital and regional indicator forms
+    Lookalike,
+    /// Upside-down letters from [`Style::Inverted`]
+    Inverted,
+}
+
+/// Styles whose forms are in everyday use: fullwidth text in CJK writing and
+/// raised or lowered digits in units and formulas
+const EVERYDAY_STYLES: [Style; 3] = [Style::Fullwidth, Style::Superscript, Style::Subscript];
+
+/// Styled character → (plain character, origin), built from the coverage of
+/// every style but [`EVERYDAY_STYLES`]
+fn styled_chars() -> &'static HashMap<char, (char, Origin)> {
+    static TABLE: OnceLock<HashMap<char, (char, Origin)>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = HashMap::new();
+        // Inverted last, so forms shared with other styles keep their meaning
+        let styles = Style::ALL
+            .iter()
+            .copied()
+            .filter(|s| *s != Style::Inverted && !EVERYDAY_STYLES.contains(s))
+            .chain([Style::Inverted]);
+        for s in styles {
+            for (plain, styled) in s.coverage() {
+                let origin = match styled as u32 {
+                    _ if s == Style::Inverted => Origin::Inverted,
+                    0x1D400..=0x1D7FF | 0x2100..=0x214F => Origin::Math,
+                    _ => Origin::Lookalike,
+                };
+                if plain == styled || styled.is_ascii() {
+                    continue;
+                }
+                // Single-case styles map both cases to one form; prefer
+                // lowercase, except for regional indicators (country codes)
+                let entry = table.entry(styled).or_insert((plain, origin));
+                let lowercase = s != Style::RegionalIndicator;
+                if lowercase && entry.0 == plain.to_ascii_uppercase() && entry.0 != plain {
+                    entry.0 = plain;
+                }
+            }
+        }
+        table
+    })
+}
+
+/// Glyph character → glyph name
+fn glyph_chars() -> &'static HashMap<char, &'static str> {
+    static TABLE: OnceLock<HashMap<char, &'static str>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = HashMap::new();
+        for &name in GLYPH_NAMES {
+            let mut chars = glyph(name).unwrap_or_default().chars();
+            if let (Some(c), Some('\u{FE0E}') | None) = (chars.next(), chars.next()) {
+                if !c.is_ascii() {
+                    table.entry(c).or_insert(name);
+                }
+            }
+        }
+        table
+    })
+}
+
+/// Overlay, underline and overline marks added by effects
+fn is_effect_mark(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0305}' | '\u{0332}' | '\u{0333}' | '\u{0334}'..='\u{0338}'
+    )
+}
+
+/// Recover plain text from styled text, for use as alt text
+///
+/// Styled letters, digits and symbols become their plain forms. Upside-down
+/// letters (which double as IPA symbols) are kept, as are fullwidth,
+/// superscript and subscript forms, which ordinary text uses too. Effect marks are dropped, as are all marks on clusters
+/// carrying more than one (as produced by [`zalgo`](crate::zalgo)); a single
+/// accent stays. Separators between regional indicator letters are removed.
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{style, unstyle, Style};
+///
+/// assert_eq!(unstyle(&style("Bold №1", Style::MathBold)), "Bold №1");
+/// assert_eq!(unstyle("ꜱᴍᴀʟʟ ⓒⓐⓟⓢ s̶t̶r̶i̶k̶e̶"), "small caps strike");
+/// ```
+pub fn unstyle(text: &str) -> String {
+    let table = styled_chars();
+    let mut out = String::with_capacity(text.len());
+    for cluster in graphemes(text) {
+        let mut chars = cluster.chars();
+        let Some(base) = chars.next() else {
+            continue;
+        };
+        let mapped = table
+            .get(&base)
+            .filter(|(_, origin)| *origin != Origin::Inverted);
+        out.push(mapped.map_or(base, |&(plain, _)| plain));
+
+        let marks = chars.as_str();
+        let combining = marks
+            .chars()
+            .filter(|c| ('\u{0300}'..='\u{036F}').contains(c))
+            .count();
+        let is_regional = mapped.is_some() && ('\u{1F1E6}'..='\u{1F1FF}').contains(&base);
+        for c in marks.chars() {
+            let drop = match c {
+                '\u{0300}'..='\u{036F}' => combining > 1 || is_effect_mark(c),
+                '\u{200B}' | '\u{200C}' => is_regional,
+                _ => false,
+            };
+            if !drop {
+                out.push(c);
+            }
+        }
+    }
+    out
+}
+
+/// Why [`lint_accessibility`] flagged part of a string
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum A11yIssueKind {
+    /// Mathematical alphanumerics, announced as "mathematical bold capital H"
+    MathAlphanumeric,
+    /// Enclosed or small capital letters, announced by name
+    LookalikeLetter,
+    /// Upside-down letters, announced as phonetic symbols or skipped
+    Inverted,
+    /// Stacked or overlaid combining marks, each announced or garbling the letter
+    CombiningMarks,
+    /// A symbol from the glyph table, announced by its Unicode name
+    Glyph(&'static str),
+}
+
+impl A11yIssueKind {
+    /// Penalty per grapheme cluster, out of [`MAX_WEIGHT`]
+    const fn weight(self) -> u32 {
+        match self {
+            A11yIssueKind::CombiningMarks => 3,
+            A11yIssueKind::MathAlphanumeric
+            | A11yIssueKind::LookalikeLetter
+            | A11yIssueKind::Inverted => 2,
+            A11yIssueKind::Glyph(_) => 1,
+        }
+    }
+}
+
+const MAX_WEIGHT: u32 = 3;
+
+/// A run of adjacent grapheme clusters with the same problem
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct A11yIssue {
+    /// Why the range was flagged
+    pub kind: A11yIssueKind,
+    /// Byte range in the input text
+    pub range: Range<usize>,
+}
+
+/// Output of [`lint_accessibility`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct A11yReport {
+    /// 0 when the text reads naturally, up to 100 when nothing does
+    pub score: u8,
+    /// Flagged runs, in input order
+    pub issues: Vec<A11yIssue>,
+}
+
+/// Score how badly a screen reader will read `text` aloud
+///
+/// Each non-whitespace grapheme cluster is penalized by its worst problem:
+/// stacked or effect marks count 3, styled or inverted letters 2, and glyphs
+/// count 1. The score is the total as a percentage of 3 per cluster, so plain
+/// text scores 0 and fully Zalgo'd text scores 100.
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{lint_accessibility, style, A11yIssueKind, Style};
+///
+/// assert_eq!(lint_accessibility("Plain text, ça va?").score, 0);
+///
+/// let report = lint_accessibility(&format!("{} ★", style("Hi", Style::MathBold)));
+/// assert_eq!(report.score, 55);
+/// assert_eq!(report.issues[0].kind, A11yIssueKind::MathAlphanumeric);
+/// assert_eq!(report.issues[1].kind, A11yIssueKind::Glyph("star"));
+/// ```
+pub fn lint_accessibility(text: &str) -> A11yReport {
+    let styled = styled_chars();
+    let glyphs = glyph_chars();
+    let mut issues: Vec<A11yIssue> = Vec::new();
+    let mut penalty = 0;
+    let mut clusters = 0;
+    let mut offset = 0;
+    for cluster in graphemes(text) {
+        let start = offset;
+        offset += cluster.len();
+        let Some(base) = cluster.chars().next() else {
+            continue;
+        };
+        if cluster.chars().all(char::is_whitespace) {
+            continue;
+        }
+        clusters += 1;
+
+        let marks = cluster
+            .chars()
+            .skip(1)
+            .filter(|c| ('\u{0300}'..='\u{036F}').contains(c));
+        let kind = if marks.clone().count() > 1 || marks.clone().any(is_effect_mark) {
+            Some(A11yIssueKind::CombiningMarks)
+        } else if let Some(&(_, origin)) = styled.get(&base) {
+            Some(match origin {
+                Origin::Math => A11yIssueKind::MathAlphanumeric,
+                Origin::Lookalike => A11yIssueKind::LookalikeLetter,
+                Origin::Inverted => A11yIssueKind::Inverted,
+            })
+        } else {
+            glyphs.get(&base).map(|&name| A11yIssueKind::Glyph(name))
+        };
+        let Some(kind) = kind else {
+            continue;
+        };
+        penalty += kind.weight();
+        match issues.last_mut() {
+            Some(last) if last.kind == kind && last.range.end == start => last.range.end = offset,
+            _ => issues.push(A11yIssue {
+                kind,
+                range: start..offset,
+            }),
+        }
+    }
+    let score = if clusters == 0 {
+        0
+    } else {
+        (penalty * 100 / (clusters * MAX_WEIGHT)).min(100) as u8
+    };
+    A11yReport { score, issues }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{zalgo, ZalgoIntensity};
+
+    #[test]
+    fn test_unstyle_every_style() {
+        for &s in Style::ALL {
+            if matches!(s, Style::Inverted) || EVERYDAY_STYLES.contains(&s) {
+                continue;
+            }
+            let text = "Sphinx of black quartz 0123";
+            // Single-case styles come back lowercase
+            let expected: String = text
+                .chars()
+                .map(|c| {
+                    if s.supports(c) {
+                        c.to_ascii_lowercase()
+                    } else {
+                        '?'
+                    }
+                })
+                .collect();
+            let round_trip: String = unstyle(&style(text, s))
+                .chars()
+                .zip(text.chars())
+                .map(|(back, c)| {
+                    if s.supports(c) {
+                        back.to_ascii_lowercase()
+                    } else {
+                        '?'
+                    }
+                })
+                .collect();
+            assert_eq!(round_trip, expected, "{s}");
+        }
+    }
+
+    #[test]
+    fn test_unstyle_marks() {
+        assert_eq!(
+            unstyle(&zalgo("glitch", ZalgoIntensity::Heavy, 9)),
+            "glitch"
+        );
+        assert_eq!(unstyle("e\u{0301}"), "e\u{0301}");
+        assert_eq!(unstyle(&style("UK", Style::RegionalIndicator)), "UK");
+        assert_eq!(unstyle("ab\u{200C}c"), "ab\u{200C}c");
+    }
+
+    #[test]
+    fn test_everyday_forms_left_alone() {
+        let text = "ＡＢ、日本\u{3000}m² H₂O";
+        assert_eq!(unstyle(text), text);
+        // Raised digits are only the lower-severity glyph issue
+        let kinds: Vec<_> = lint_accessibility(text)
+            .issues
+            .iter()
+            .map(|i| i.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            [A11yIssueKind::Glyph("sup.2"), A11yIssueKind::Glyph("sub.2")]
+        );
+    }
+
+    #[test]
+    fn test_lint_kinds() {
+        let text = format!(
+            "{}{}{}",
+            style("ae", Style::Inverted),
+            style("ab", Style::Circled),
+            zalgo("x", ZalgoIntensity::Heavy, 1)
+        );
+        let report = lint_accessibility(&text);
+        let kinds: Vec<_> = report.issues.iter().map(|i| i.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                A11yIssueKind::Inverted,
+                A11yIssueKind::LookalikeLetter,
+                A11yIssueKind::CombiningMarks
+            ]
+        );
+        assert_eq!(report.issues[0].range, 0..4);
+        assert_eq!(report.issues[1].range, 4..10);
+        // (2 + 2 + 2 + 2 + 3) / (5 * 3)
+        assert_eq!(report.score, 73);
+    }
+
+    #[test]
+    fn test_html_escaping() {
+        let a = Accessible::new("<𝐛>", "\"b\" & co");
+        assert_eq!(
+            a.to_html(),
+            "<span aria-label=\"&quot;b&quot; &amp; co\">&lt;𝐛&gt;</span>"
+        );
+    }
+}
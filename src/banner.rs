@@ -0,0 +1,416 @@
+use crate::cells::{braille, braille_bit, glyph_char, half_block, quadrant};
+use crate::decompose::decompose;
+use crate::grapheme::graphemes;
+
+/// Pixel rows in every bitmap of the built-in font
+const HEIGHT: usize = 5;
+
+/// Built-in 5-pixel-high bitmaps, `#` for a set pixel
+///
+/// Glyphs are proportional: the width is the length of the rows.
+const FONT: &[(char, [&str; HEIGHT])] = &[
+    ('A', [".###.", "#...#", "#####", "#...#", "#...#"]),
+    ('B', ["####.", "#...#", "####.", "#...#", "####."]),
+    ('C', [".####", "#....", "#....", "#....", ".####"]),
+    ('D', ["####.", "#...#", "#...#", "#...#", "####."]),
+    ('E', ["#####", "#....", "####.", "#....", "#####"]),
+    ('F', ["#####", "#....", "####.", "#....", "#...."]),
+    ('G', [".####", "#....", "#..##", "#...#", ".###."]),
+    ('H', ["#...#", "#...#", "#####", "#...#", "#...#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["....#", "....#", "....#", "#...#", ".###."]),
+    ('K', ["#...#", "#..#.", "###..", "#..#.", "#...#"]),
+    ('L', ["#....", "#....", "#....", "#....", "#####"]),
+    ('M', ["#...#", "##.##", "#.#.#", "#...#", "#...#"]),
+    ('N', ["#...#", "##..#", "#.#.#", "#..##", "#...#"]),
+    ('O', [".###.", "#...#", "#...#", "#...#", ".###."]),
+    ('P', ["####.", "#...#", "####.", "#....", "#...."]),
+    ('Q', [".###.", "#...#", "#.#.#", "#..#.", ".##.#"]),
+    ('R', ["####.", "#...#", "####.", "#..#.", "#...#"]),
+    ('S', [".####", "#....", ".###.", "....#", "####."]),
+    ('T', ["#####", "..#..", "..#..", "..#..", "..#.."]),
+    ('U', ["#...#", "#...#", "#...#", "#...#", ".###."]),
+    ('V', ["#...#", "#...#", "#...#", ".#.#.", "..#.."]),
+    ('W', ["#...#", "#...#", "#.#.#", "##.##", "#...#"]),
+    ('X', ["#...#", ".#.#.", "..#..", ".#.#.", "#...#"]),
+    ('Y', ["#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["#####", "...#.", "..#..", ".#...", "#####"]),
+    ('0', [".###.", "#..##", "#.#.#", "##..#", ".###."]),
+    ('1', [".#.", "##.", ".#.", ".#.", "###"]),
+    ('2', ["####.", "....#", ".###.", "#....", "#####"]),
+    ('3', ["####.", "....#", ".###.", "....#", "####."]),
+    ('4', ["#...#", "#...#", "#####", "....#", "....#"]),
+    ('5', ["#####", "#....", "####.", "....#", "####."]),
+    ('6', [".###.", "#....", "####.", "#...#", ".###."]),
+    ('7', ["#####", "....#", "...#.", "..#..", "..#.."]),
+    ('8', [".###.", "#...#", ".###.", "#...#", ".###."]),
+    ('9', [".###.", "#...#", ".####", "....#", ".###."]),
+    (' ', ["...", "...", "...", "...", "..."]),
+    ('!', ["#", "#", "#", ".", "#"]),
+    ('?', ["###.", "...#", ".##.", "....", ".#.."]),
+    ('.', [".", ".", ".", ".", "#"]),
+    (',', ["..", "..", "..", ".#", "#."]),
+    (':', [".", "#", ".", "#", "."]),
+    (';', ["..", ".#", "..", ".#", "#."]),
+    ('\'', ["#", "#", ".", ".", "."]),
+    ('"', ["#.#", "#.#", "...", "...", "..."]),
+    ('-', ["...", "...", "###", "...", "..."]),
+    ('+', ["...", ".#.", "###", ".#.", "..."]),
+    ('=', ["...", "###", "...", "###", "..."]),
+    ('*', ["...", "#.#", ".#.", "#.#", "..."]),
+    ('_', ["....", "....", "....", "....", "####"]),
+    ('/', ["..#", "..#", ".#.", "#..", "#.."]),
+    ('%', ["#.#", "..#", ".#.", "#..", "#.#"]),
+    ('#', [".#.#.", "#####", ".#.#.", "#####", ".#.#."]),
+    ('(', [".#", "#.", "#.", "#.", ".#"]),
+    (')', ["#.", ".#", ".#", ".#", "#."]),
+    ('[', ["##", "#.", "#.", "#.", "##"]),
+    (']', ["##", ".#", ".#", ".#", "##"]),
+    ('<', ["..#", ".#.", "#..", ".#.", "..#"]),
+    ('>', ["#..", ".#.", "..#", ".#.", "#.."]),
+];
+
+/// How [`banner`] draws the pixels of its built-in letters
+///
+/// Every font draws the same 5-pixel-high letters at a different density, so
+/// the heights below are in text lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BannerFont {
+    /// One `block.full` cell per pixel, 5 lines high
+    #[default]
+    Block,
+
+    /// Two pixels stacked in each cell with `block.upper.4` and
+    /// `block.lower.4`, 3 lines high
+    HalfBlock,
+
+    /// A 2×2 square of pixels in each cell with the `quad.*` glyphs, 3 lines
+    /// high and half as wide
+    Quadrant,
+
+    /// A 2×4 square of pixels in each braille cell, 2 lines high and half as
+    /// wide
+    Braille,
+
+    /// One `#` per pixel, for terminals and logs without Unicode
+    Ascii,
+}
+
+impl BannerFont {
+    /// Pixels across and down each text cell
+    const fn cell_size(self) -> (usize, usize) {
+        match self {
+            BannerFont::Block | BannerFont::Ascii => (1, 1),
+            BannerFont::HalfBlock => (1, 2),
+            BannerFont::Quadrant => (2, 2),
+            BannerFont::Braille => (2, 4),
+        }
+    }
+}
+
+/// Large-letter banner settings
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{Banner, BannerFont};
+///
+/// let art = Banner::new(BannerFont::Ascii).max_width(12).render("HI HI");
+/// // "HI HI" is 21 columns wide, so it wraps onto two banners
+/// assert_eq!(art.lines().count(), 11);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Banner {
+    font: BannerFont,
+    spacing: usize,
+    kerning: bool,
+    max_width: Option<usize>,
+}
+
+impl Banner {
+    /// Banner drawn in `font`, with one pixel between letters and no width limit
+    pub fn new(font: BannerFont) -> Self {
+        Banner {
+            font,
+            spacing: 1,
+            kerning: false,
+            max_width: None,
+        }
+    }
+
+    /// Blank pixel columns between letters
+    pub fn spacing(mut self, pixels: usize) -> Self {
+        self.spacing = pixels;
+        self
+    }
+
+    /// Move letters together until they are `spacing` pixels apart, as FIGlet
+    /// does
+    ///
+    /// Letter shapes may then overlap, such as the bar of a `T` reaching over
+    /// the foot of an `L`. Off by default; words stay apart either way.
+    pub fn kerning(mut self, kerning: bool) -> Self {
+        self.kerning = kerning;
+        self
+    }
+
+    /// Wrap to more banners below instead of exceeding `columns` text columns
+    ///
+    /// Lines break between words, or between letters for a word too wide on
+    /// its own. A single letter is never split.
+    pub fn max_width(mut self, columns: usize) -> Self {
+        self.max_width = Some(columns);
+        self
+    }
+
+    /// Draw `text` in large letters
+    ///
+    /// Each line of `text` (and each wrapped piece of it) becomes a banner,
+    /// with a blank line between banners. Lowercase letters are drawn as
+    /// capitals, accents are dropped, and characters the font lacks are drawn
+    /// as `?`. Trailing spaces are trimmed from every line.
+    pub fn render(&self, text: &str) -> String {
+        let banners: Vec<String> = text
+            .lines()
+            .flat_map(|line| self.wrap(line))
+            .map(|line| self.draw(&self.layout(&line)))
+            .collect();
+        banners.join("\n\n")
+    }
+
+    /// Width of `text` in text columns
+    fn columns(&self, text: &str) -> usize {
+        let (across, _) = self.font.cell_size();
+        (self.layout(text)[0].len() + across - 1) / across
+    }
+
+    /// Split a line into pieces that fit the width limit
+    fn wrap(&self, line: &str) -> Vec<String> {
+        let Some(max) = self.max_width else {
+            return vec![line.to_string()];
+        };
+        let mut pieces = Vec::new();
+        let mut current = String::new();
+        for word in line.split_whitespace() {
+            let candidate = if current.is_empty() {
+                word.to_string()
+            } else {
+                format!("{current} {word}")
+            };
+            if self.columns(&candidate) <= max {
+                current = candidate;
+                continue;
+            }
+            if !current.is_empty() {
+                pieces.push(std::mem::take(&mut current));
+            }
+            for cluster in graphemes(word) {
+                let candidate = format!("{current}{cluster}");
+                if !current.is_empty() && self.columns(&candidate) > max {
+                    pieces.push(std::mem::replace(&mut current, cluster.to_string()));
+                } else {
+                    current = candidate;
+                }
+            }
+        }
+        if !current.is_empty() || pieces.is_empty() {
+            pieces.push(current);
+        }
+        pieces
+    }
+
+    /// Place the letters of `text` on [`HEIGHT`] rows of pixels
+    fn layout(&self, text: &str) -> Vec<Vec<bool>> {
+        let mut rows = vec![Vec::new(); HEIGHT];
+        let mut prev: Option<(usize, bool)> = None;
+        for bitmap in bitmaps(text) {
+            let width = bitmap[0].len();
+            let blank = bitmap.iter().all(|row| !row.contains('#'));
+            let end = rows[0].len();
+            let mut start = match prev {
+                Some(_) => end + self.spacing,
+                None => 0,
+            };
+            if let Some((prev_start, false)) = prev.filter(|_| self.kerning && !blank) {
+                // Leftmost start keeping `spacing` clear pixels on every row
+                let mut fit = prev_start + 1;
+                for (row, pixels) in rows.iter().zip(bitmap) {
+                    if let (Some(left), Some(right)) =
+                        (pixels.find('#'), row.iter().rposition(|&p| p))
+                    {
+                        fit = fit.max((right + 1 + self.spacing).saturating_sub(left));
+                    }
+                }
+                start = start.min(fit);
+            }
+            let end = end.max(start + width);
+            for (row, pixels) in rows.iter_mut().zip(bitmap) {
+                row.resize(end, false);
+                for (i, pixel) in pixels.bytes().enumerate() {
+                    row[start + i] |= pixel == b'#';
+                }
+            }
+            prev = Some((start, blank));
+        }
+        rows
+    }
+
+    /// Render rows of pixels as lines of text cells
+    fn draw(&self, rows: &[Vec<bool>]) -> String {
+        let pixel = |y: usize, x: usize| rows.get(y).and_then(|row| row.get(x)) == Some(&true);
+        let (across, down) = self.font.cell_size();
+        let width = (rows[0].len() + across - 1) / across;
+        let mut lines = Vec::new();
+        for y in (0..HEIGHT).step_by(down) {
+            let line: String = (0..width)
+                .map(|cell| {
+                    let x = cell * across;
+                    match self.font {
+                        BannerFont::Block if pixel(y, x) => glyph_char("block.full"),
+                        BannerFont::Ascii if pixel(y, x) => '#',
+                        BannerFont::Block | BannerFont::Ascii => ' ',
+                        BannerFont::HalfBlock => half_block(pixel(y, x), pixel(y + 1, x)),
+                        BannerFont::Quadrant => quadrant(
+                            u8::from(pixel(y, x))
+                                | u8::from(pixel(y, x + 1)) << 1
+                                | u8::from(pixel(y + 1, x)) << 2
+                                | u8::from(pixel(y + 1, x + 1)) << 3,
+                        ),
+                        BannerFont::Braille => {
+                            let mut mask = 0;
+                            for dy in 0..4 {
+                                for dx in 0..2 {
+                                    if pixel(y + dy, x + dx) {
+                                        mask |= braille_bit(dy, dx);
+                                    }
+                                }
+                            }
+                            if mask == 0 {
+                                ' '
+                            } else {
+                                braille(mask)
+                            }
+                        }
+                    }
+                })
+                .collect();
+            lines.push(line.trim_end().to_string());
+        }
+        lines.join("\n")
+    }
+}
+
+impl Default for Banner {
+    fn default() -> Self {
+        Banner::new(BannerFont::default())
+    }
+}
+
+/// Bitmaps for the letters of `text`
+///
+/// Accented letters use the bitmap of their base letter and whitespace is a
+/// space; anything else the font lacks becomes `?`.
+fn bitmaps(text: &str) -> impl Iterator<Item = &'static [&'static str; HEIGHT]> + '_ {
+    graphemes(text).flat_map(|cluster| {
+        let base = cluster.chars().next().unwrap_or(' ');
+        let letters: Vec<char> = if base.is_whitespace() {
+            vec![' ']
+        } else if let Some(parts) = decompose(base) {
+            parts.chars().filter(char::is_ascii_alphanumeric).collect()
+        } else {
+            vec![base]
+        };
+        letters.into_iter().map(|c| {
+            let c = c.to_ascii_uppercase();
+            FONT.iter()
+                .find(|(key, _)| *key == c)
+                .or_else(|| FONT.iter().find(|(key, _)| *key == '?'))
+                .map(|(_, bitmap)| bitmap)
+                .expect("font has '?'")
+        })
+    })
+}
+
+/// Draw `text` in large letters with the default [`Banner`] settings
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{banner, BannerFont};
+///
+/// assert_eq!(
+///     banner("Hi!", BannerFont::Ascii),
+///     "#   # ### #\n#   #  #  #\n#####  #  #\n#   #  #\n#   # ### #"
+/// );
+/// assert_eq!(banner("Hi!", BannerFont::Braille), "⡧⠤⡇⢹⠁⠇\n⠁ ⠁⠉⠁⠁");
+/// ```
+pub fn banner(text: &str, font: BannerFont) -> String {
+    Banner::new(font).render(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_font_is_consistent() {
+        for (c, bitmap) in FONT {
+            let width = bitmap[0].len();
+            assert!(bitmap.iter().all(|row| row.len() == width), "{c:?}");
+            assert!(bitmap
+                .iter()
+                .all(|row| row.bytes().all(|b| b"#.".contains(&b))));
+        }
+        for c in ('A'..='Z')
+            .chain('0'..='9')
+            .chain("!?.,:;'\"-+=*_/%#()[]<>".chars())
+        {
+            assert!(FONT.iter().any(|(key, _)| *key == c), "{c:?}");
+        }
+    }
+
+    #[test]
+    fn test_fonts_share_letters() {
+        let block = banner("AB", BannerFont::Block);
+        assert_eq!(block.replace('█', "#"), banner("ab", BannerFont::Ascii));
+        assert_eq!(block.lines().count(), 5);
+        assert_eq!(banner("AB", BannerFont::HalfBlock).lines().count(), 3);
+        let quad = banner("AB", BannerFont::Quadrant);
+        assert_eq!(quad, "▞▀▖▛▀▖\n▛▀▌▛▀▖\n▘ ▘▀▀");
+    }
+
+    #[test]
+    fn test_kerning_and_spacing() {
+        let plain = Banner::new(BannerFont::Ascii);
+        assert_eq!(plain.columns("LT"), 11);
+        assert_eq!(plain.kerning(true).columns("LT"), 9);
+        assert_eq!(plain.spacing(3).columns("LT"), 13);
+        // Kerning never pulls a letter over a space
+        assert_eq!(plain.kerning(true).columns("L T"), plain.columns("L T"));
+    }
+
+    #[test]
+    fn test_wrapping() {
+        let banner = Banner::new(BannerFont::Block).max_width(11);
+        let art = banner.render("AB CD\nE");
+        let parts: Vec<&str> = art.split("\n\n").collect();
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[1], super::banner("CD", BannerFont::Block));
+        assert!(art.lines().all(|line| line.chars().count() <= 11));
+        // Long words break between letters
+        assert_eq!(banner.render("ABCD").split("\n\n").count(), 2);
+    }
+
+    #[test]
+    fn test_fallbacks() {
+        assert_eq!(
+            banner("é", BannerFont::Ascii),
+            banner("E", BannerFont::Ascii)
+        );
+        assert_eq!(
+            banner("ж", BannerFont::Ascii),
+            banner("?", BannerFont::Ascii)
+        );
+        assert_eq!(banner("", BannerFont::Block), "");
+    }
+}
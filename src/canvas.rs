@@ -0,0 +1,589 @@
+use crate::cells::{braille, braille_bit, half_block, quadrant, sextant};
+use std::fmt;
+
+/// A grid of on/off pixels drawn as text
+///
+/// Implementors store the pixels and render them; the drawing primitives are
+/// provided. Drawing coordinates are signed so shapes may run off the edge:
+/// pixels outside the canvas are ignored. `x` grows to the right and `y`
+/// downwards from the top left corner.
+pub trait Canvas {
+    /// Width in pixels
+    fn width(&self) -> usize;
+
+    /// Height in pixels
+    fn height(&self) -> usize;
+
+    /// Whether the pixel at `x`, `y` is on; both are within the canvas
+    fn pixel(&self, x: usize, y: usize) -> bool;
+
+    /// Turn the pixel at `x`, `y` on or off; both are within the canvas
+    fn set_pixel(&mut self, x: usize, y: usize, on: bool);
+
+    /// The canvas as lines of text, one per row of cells
+    fn render(&self) -> String;
+
+    /// Whether the pixel at `x`, `y` is on, `false` outside the canvas
+    fn get(&self, x: i32, y: i32) -> bool {
+        match index(self, x, y) {
+            Some((x, y)) => self.pixel(x, y),
+            None => false,
+        }
+    }
+
+    /// Turn a pixel on
+    fn set(&mut self, x: i32, y: i32) {
+        if let Some((x, y)) = index(self, x, y) {
+            self.set_pixel(x, y, true);
+        }
+    }
+
+    /// Turn a pixel off
+    fn unset(&mut self, x: i32, y: i32) {
+        if let Some((x, y)) = index(self, x, y) {
+            self.set_pixel(x, y, false);
+        }
+    }
+
+    /// Flip a pixel
+    fn toggle(&mut self, x: i32, y: i32) {
+        if let Some((x, y)) = index(self, x, y) {
+            let on = self.pixel(x, y);
+            self.set_pixel(x, y, !on);
+        }
+    }
+
+    /// Turn every pixel off
+    fn clear(&mut self) {
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                self.set_pixel(x, y, false);
+            }
+        }
+    }
+
+    /// Draw a line between two points, both included (Bresenham's algorithm)
+    ///
+    /// Lines running off the canvas are clipped to its edges first, so only
+    /// the visible part is walked.
+    fn line(&mut self, from: (i32, i32), to: (i32, i32)) {
+        let Some(((mut x, mut y), (x1, y1))) = clip_line(self, from, to) else {
+            return;
+        };
+        let dx = (x1 - x).abs();
+        let dy = -(y1 - y).abs();
+        let (step_x, step_y) = ((x1 - x).signum(), (y1 - y).signum());
+        let mut err = dx + dy;
+        loop {
+            set_wide(self, x, y);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let doubled = 2 * err;
+            if doubled >= dy {
+                err += dy;
+                x += step_x;
+            }
+            if doubled <= dx {
+                err += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    /// Draw the outline of a rectangle with its top left corner at `x`, `y`
+    ///
+    /// Nothing is drawn if either size is 0.
+    fn rectangle(&mut self, x: i32, y: i32, width: u32, height: u32) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        let right = i32::try_from(i64::from(x) + i64::from(width) - 1).unwrap_or(i32::MAX);
+        let bottom = i32::try_from(i64::from(y) + i64::from(height) - 1).unwrap_or(i32::MAX);
+        self.polygon(&[(x, y), (right, y), (right, bottom), (x, bottom)]);
+    }
+
+    /// Draw the outline of a circle (midpoint algorithm)
+    ///
+    /// A radius of 0 draws the center pixel alone.
+    fn circle(&mut self, (cx, cy): (i32, i32), radius: u32) {
+        let (cx, cy) = (i64::from(cx), i64::from(cy));
+        let (mut x, mut y) = (i64::from(radius), 0);
+        let mut err = 1 - x;
+        while x >= y {
+            for (dx, dy) in [(x, y), (y, x)] {
+                for (sx, sy) in [(1, 1), (-1, 1), (1, -1), (-1, -1)] {
+                    set_wide(self, cx + sx * dx, cy + sy * dy);
+                }
+            }
+            y += 1;
+            if err < 0 {
+                err += 2 * y + 1;
+            } else {
+                x -= 1;
+                err += 2 * (y - x) + 1;
+            }
+        }
+    }
+
+    /// Draw the closed outline through `points`, joining the last to the first
+    fn polygon(&mut self, points: &[(i32, i32)]) {
+        for (i, &from) in points.iter().enumerate() {
+            self.line(from, points[(i + 1) % points.len()]);
+        }
+    }
+}
+
+/// Canvas coordinates of a drawing position, if it is on the canvas
+fn index<C: Canvas + ?Sized>(canvas: &C, x: i32, y: i32) -> Option<(usize, usize)> {
+    let x = usize::try_from(x).ok().filter(|&x| x < canvas.width())?;
+    let y = usize::try_from(y).ok().filter(|&y| y < canvas.height())?;
+    Some((x, y))
+}
+
+/// The part of a line within one pixel of the canvas, or `None` if it misses
+///
+/// Endpoints already that close are kept exactly (Liang–Barsky clipping).
+fn clip_line<C: Canvas + ?Sized>(
+    canvas: &C,
+    (x0, y0): (i32, i32),
+    (x1, y1): (i32, i32),
+) -> Option<((i64, i64), (i64, i64))> {
+    let (x0, y0, x1, y1) = (f64::from(x0), f64::from(y0), f64::from(x1), f64::from(y1));
+    let (dx, dy) = (x1 - x0, y1 - y0);
+    let (right, bottom) = (canvas.width() as f64, canvas.height() as f64);
+    let (mut enter, mut leave) = (0.0_f64, 1.0_f64);
+    // Each edge as (-direction, distance) towards the inside
+    for (p, q) in [
+        (-dx, x0 + 1.0),
+        (dx, right - x0),
+        (-dy, y0 + 1.0),
+        (dy, bottom - y0),
+    ] {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+        } else if p < 0.0 {
+            enter = enter.max(q / p);
+        } else {
+            leave = leave.min(q / p);
+        }
+    }
+    if enter > leave {
+        return None;
+    }
+    let point = |t: f64| ((x0 + t * dx).round() as i64, (y0 + t * dy).round() as i64);
+    Some((point(enter), point(leave)))
+}
+
+/// Turn on a pixel given in the wider coordinates used while drawing
+fn set_wide<C: Canvas + ?Sized>(canvas: &mut C, x: i64, y: i64) {
+    if let (Ok(x), Ok(y)) = (i32::try_from(x), i32::try_from(y)) {
+        canvas.set(x, y);
+    }
+}
+
+/// A pixel canvas drawn with braille patterns, 2×4 pixels per character
+///
+/// Braille gives the highest resolution of any text canvas: a 40-column,
+/// 10-line area holds 80×40 pixels. Every cell of the canvas is rendered, with
+/// U+2800 BRAILLE PATTERN BLANK for empty cells, so lines keep their width.
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{BrailleCanvas, Canvas};
+///
+/// let mut canvas = BrailleCanvas::new(8, 4);
+/// canvas.line((0, 0), (7, 3));
+/// assert_eq!(canvas.render(), "⠉⠒⠤⣀");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BrailleCanvas {
+    width: usize,
+    height: usize,
+    /// Dot masks of the cells, row by row
+    cells: Vec<u8>,
+}
+
+impl BrailleCanvas {
+    /// Blank canvas of `width` × `height` pixels
+    pub fn new(width: usize, height: usize) -> Self {
+        BrailleCanvas {
+            width,
+            height,
+            cells: vec![0; Self::columns_for(width) * ((height + 3) / 4)],
+        }
+    }
+
+    /// Columns needed for `width` pixels
+    fn columns_for(width: usize) -> usize {
+        (width + 1) / 2
+    }
+
+    fn cell(&self, x: usize, y: usize) -> (usize, u8) {
+        let index = y / 4 * Self::columns_for(self.width) + x / 2;
+        (index, braille_bit(y % 4, x % 2))
+    }
+}
+
+impl Canvas for BrailleCanvas {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn pixel(&self, x: usize, y: usize) -> bool {
+        let (index, bit) = self.cell(x, y);
+        self.cells[index] & bit != 0
+    }
+
+    fn set_pixel(&mut self, x: usize, y: usize, on: bool) {
+        let (index, bit) = self.cell(x, y);
+        if on {
+            self.cells[index] |= bit;
+        } else {
+            self.cells[index] &= !bit;
+        }
+    }
+
+    fn render(&self) -> String {
+        let columns = Self::columns_for(self.width);
+        if columns == 0 {
+            return String::new();
+        }
+        let lines: Vec<String> = self
+            .cells
+            .chunks(columns)
+            .map(|row| row.iter().map(|&mask| braille(mask)).collect())
+            .collect();
+        lines.join("\n")
+    }
+
+    fn clear(&mut self) {
+        self.cells.fill(0);
+    }
+}
+
+impl fmt::Display for BrailleCanvas {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render())
+    }
+}
+
+/// Block characters a terminal font is known to draw
+///
+/// Ordered from the smallest character set to the largest, so
+/// `profile >= RenderProfile::Standard` works.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum RenderProfile {
+    /// Half blocks only (`▀`, `▄`, `█`), as in code page 437 console fonts
+    Legacy,
+
+    /// Every block element, including the quadrants
+    #[default]
+    Standard,
+
+    /// Symbols for Legacy Computing (Unicode 13) as well, for sextants
+    Modern,
+}
+
+/// Pixels per character of a [`BlockCanvas`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum BlockMode {
+    /// 1×2 pixels with `block.upper.4`, `block.lower.4` and `block.full`
+    HalfBlock,
+
+    /// 2×2 pixels with all 16 quadrant patterns
+    #[default]
+    Quadrant,
+
+    /// 2×3 pixels with the sextants of Symbols for Legacy Computing
+    Sextant,
+}
+
+impl BlockMode {
+    /// Pixels across and down each character
+    pub const fn cell_size(self) -> (usize, usize) {
+        match self {
+            BlockMode::HalfBlock => (1, 2),
+            BlockMode::Quadrant => (2, 2),
+            BlockMode::Sextant => (2, 3),
+        }
+    }
+
+    /// The smallest profile that draws this mode
+    pub const fn profile(self) -> RenderProfile {
+        match self {
+            BlockMode::HalfBlock => RenderProfile::Legacy,
+            BlockMode::Quadrant => RenderProfile::Standard,
+            BlockMode::Sextant => RenderProfile::Modern,
+        }
+    }
+
+    /// This mode, or the next lower one that `profile` draws
+    pub const fn fallback(self, profile: RenderProfile) -> BlockMode {
+        match (self, profile) {
+            (BlockMode::Sextant, RenderProfile::Modern) => BlockMode::Sextant,
+            (BlockMode::HalfBlock, _) | (_, RenderProfile::Legacy) => BlockMode::HalfBlock,
+            _ => BlockMode::Quadrant,
+        }
+    }
+
+    /// Character for a mask with bit `row * width + col` per pixel
+    fn cell(self, mask: u8) -> char {
+        match self {
+            BlockMode::HalfBlock => half_block(mask & 1 != 0, mask & 2 != 0),
+            BlockMode::Quadrant => quadrant(mask),
+            BlockMode::Sextant => sextant(mask),
+        }
+    }
+}
+
+/// A pixel canvas drawn with block characters
+///
+/// The [`BlockMode`] sets the resolution. [`render_for`](BlockCanvas::render_for)
+/// falls back to a lower mode when a [`RenderProfile`] lacks the characters,
+/// keeping the same number of lines and columns: each lower-resolution pixel
+/// is on if any pixel it covers is on, so thin lines stay visible. Every cell
+/// is rendered, with spaces for empty cells, so lines keep their width.
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{BlockCanvas, BlockMode, Canvas, RenderProfile};
+///
+/// let mut canvas = BlockCanvas::new(4, 3, BlockMode::Sextant);
+/// canvas.line((0, 0), (3, 2));
+/// assert_eq!(canvas.render(), "\u{1FB08}\u{1FB22}");
+/// assert_eq!(canvas.render_for(RenderProfile::Standard), "▜▙");
+/// assert_eq!(canvas.render_for(RenderProfile::Legacy), "██");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BlockCanvas {
+    mode: BlockMode,
+    width: usize,
+    height: usize,
+    /// Pixels row by row
+    pixels: Vec<bool>,
+}
+
+impl BlockCanvas {
+    /// Blank canvas of `width` × `height` pixels drawn in `mode`
+    pub fn new(width: usize, height: usize, mode: BlockMode) -> Self {
+        BlockCanvas {
+            mode,
+            width,
+            height,
+            pixels: vec![false; width * height],
+        }
+    }
+
+    /// The resolution this canvas is drawn at
+    pub fn mode(&self) -> BlockMode {
+        self.mode
+    }
+
+    /// The canvas drawn with the characters `profile` supports
+    pub fn render_for(&self, profile: RenderProfile) -> String {
+        let target = self.mode.fallback(profile);
+        let (across, down) = self.mode.cell_size();
+        let (target_across, target_down) = target.cell_size();
+        let columns = (self.width + across - 1) / across;
+        let rows = (self.height + down - 1) / down;
+        // Source pixels from `first` to `last` overlap target pixel `i` of `n`
+        let span = |i: usize, n: usize, size: usize| (i * size / n, ((i + 1) * size - 1) / n);
+        let mut lines = Vec::with_capacity(rows);
+        for row in 0..rows {
+            let mut line = String::with_capacity(columns * 4);
+            for column in 0..columns {
+                let mut mask = 0;
+                for j in 0..target_down {
+                    let (top, bottom) = span(j, target_down, down);
+                    for i in 0..target_across {
+                        let (left, right) = span(i, target_across, across);
+                        let on = (top..=bottom).any(|y| {
+                            (left..=right).any(|x| self.lit(column * across + x, row * down + y))
+                        });
+                        if on {
+                            mask |= 1 << (j * target_across + i);
+                        }
+                    }
+                }
+                line.push(target.cell(mask));
+            }
+            lines.push(line);
+        }
+        lines.join("\n")
+    }
+
+    /// Whether a pixel is on, `false` past the edge of the last cells
+    fn lit(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.pixels[y * self.width + x]
+    }
+}
+
+impl Canvas for BlockCanvas {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn pixel(&self, x: usize, y: usize) -> bool {
+        self.pixels[y * self.width + x]
+    }
+
+    fn set_pixel(&mut self, x: usize, y: usize, on: bool) {
+        self.pixels[y * self.width + x] = on;
+    }
+
+    /// The canvas drawn at its own resolution
+    fn render(&self) -> String {
+        self.render_for(self.mode.profile())
+    }
+
+    fn clear(&mut self) {
+        self.pixels.fill(false);
+    }
+}
+
+impl fmt::Display for BlockCanvas {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixels(canvas: &impl Canvas) -> Vec<(i32, i32)> {
+        let mut on = Vec::new();
+        for y in 0..canvas.height() as i32 {
+            for x in 0..canvas.width() as i32 {
+                if canvas.get(x, y) {
+                    on.push((x, y));
+                }
+            }
+        }
+        on
+    }
+
+    #[test]
+    fn test_set_unset_toggle() {
+        let mut canvas = BrailleCanvas::new(3, 5);
+        assert_eq!(canvas.render(), "\u{2800}\u{2800}\n\u{2800}\u{2800}");
+        canvas.set(0, 0);
+        canvas.set(1, 3);
+        canvas.set(2, 4);
+        assert_eq!(canvas.render(), "⢁\u{2800}\n\u{2800}⠁");
+        canvas.toggle(0, 0);
+        canvas.toggle(0, 1);
+        canvas.unset(1, 3);
+        assert_eq!(pixels(&canvas), [(0, 1), (2, 4)]);
+        // Off-canvas pixels are ignored
+        canvas.set(-1, 0);
+        canvas.set(3, 0);
+        assert!(!canvas.get(-1, 0));
+        canvas.clear();
+        assert!(pixels(&canvas).is_empty());
+    }
+
+    #[test]
+    fn test_lines_in_every_direction() {
+        for (from, to) in [((0, 0), (5, 2)), ((5, 2), (0, 0)), ((1, 5), (3, 0))] {
+            let mut canvas = BrailleCanvas::new(6, 6);
+            canvas.line(from, to);
+            let on = pixels(&canvas);
+            assert!(on.contains(&from) && on.contains(&to));
+            let span = (from.0 - to.0).abs().max((from.1 - to.1).abs()) + 1;
+            assert_eq!(on.len() as i32, span);
+        }
+        let mut canvas = BrailleCanvas::new(4, 4);
+        canvas.line((-10, -10), (10, 10));
+        assert_eq!(pixels(&canvas), [(0, 0), (1, 1), (2, 2), (3, 3)]);
+        // Far endpoints are clipped rather than walked
+        let mut canvas = BrailleCanvas::new(4, 4);
+        canvas.line((0, 1), (i32::MAX, 1));
+        canvas.line((i32::MIN, i32::MIN), (i32::MIN, i32::MAX));
+        canvas.line((2, i32::MIN), (2, i32::MAX));
+        assert_eq!(
+            pixels(&canvas),
+            [(2, 0), (0, 1), (1, 1), (2, 1), (3, 1), (2, 2), (2, 3)]
+        );
+    }
+
+    #[test]
+    fn test_block_modes() {
+        let mut canvas = BlockCanvas::new(2, 4, BlockMode::HalfBlock);
+        canvas.set(0, 0);
+        canvas.set(1, 1);
+        canvas.rectangle(0, 2, 2, 2);
+        assert_eq!(canvas.render(), "▀▄\n██");
+
+        let mut canvas = BlockCanvas::new(4, 2, BlockMode::Quadrant);
+        canvas.line((0, 0), (3, 1));
+        assert_eq!(canvas.render(), "▀▄");
+        assert_eq!(canvas.render_for(RenderProfile::Modern), "▀▄");
+
+        let mut canvas = BlockCanvas::new(3, 3, BlockMode::Sextant);
+        canvas.rectangle(0, 0, 2, 3);
+        canvas.set(2, 1);
+        assert_eq!(canvas.render(), "█\u{1FB03}");
+    }
+
+    #[test]
+    fn test_block_fallback() {
+        assert_eq!(
+            BlockMode::Sextant.fallback(RenderProfile::Standard),
+            BlockMode::Quadrant
+        );
+        assert_eq!(
+            BlockMode::Quadrant.fallback(RenderProfile::Modern),
+            BlockMode::Quadrant
+        );
+        assert_eq!(
+            BlockMode::Sextant.fallback(RenderProfile::Legacy),
+            BlockMode::HalfBlock
+        );
+        // A lone middle pixel of a sextant lights both quadrant rows
+        let mut canvas = BlockCanvas::new(2, 3, BlockMode::Sextant);
+        canvas.set(1, 1);
+        assert_eq!(canvas.render_for(RenderProfile::Standard), "▐");
+        assert_eq!(canvas.render_for(RenderProfile::Legacy), "█");
+        assert_eq!(canvas.render_for(RenderProfile::Legacy).lines().count(), 1);
+    }
+
+    #[test]
+    fn test_shapes() {
+        let mut canvas = BrailleCanvas::new(4, 4);
+        canvas.rectangle(0, 0, 4, 4);
+        assert_eq!(canvas.render(), "⣏⣹");
+        assert_eq!(pixels(&canvas).len(), 12);
+
+        let mut canvas = BrailleCanvas::new(7, 7);
+        canvas.circle((3, 3), 3);
+        let on = pixels(&canvas);
+        assert!(on.contains(&(0, 3)) && on.contains(&(6, 3)) && on.contains(&(3, 0)));
+        assert!(!on.contains(&(3, 3)));
+
+        // The drawing API is shared by every canvas
+        fn triangle(canvas: &mut impl Canvas) -> Vec<(i32, i32)> {
+            canvas.polygon(&[(0, 4), (2, 0), (4, 4)]);
+            pixels(canvas)
+        }
+        let on = triangle(&mut BrailleCanvas::new(5, 5));
+        assert!(on.contains(&(2, 0)) && on.contains(&(1, 4)) && on.contains(&(1, 2)));
+        assert_eq!(
+            on,
+            triangle(&mut BlockCanvas::new(5, 5, BlockMode::Sextant))
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use crate::glyphs::glyph;
+
+/// The character of a built-in glyph, without its VS15 selector
+///
+/// Block and braille cells are drawn side by side in grids, where a selector
+/// after every cell would only double the output size.
+pub(crate) fn glyph_char(name: &str) -> char {
+    glyph(name)
+        .ok()
+        .and_then(|g| g.chars().next())
+        .expect("built-in glyph name")
+}
+
+/// Half-block cell showing an upper and a lower pixel
+pub(crate) fn half_block(upper: bool, lower: bool) -> char {
+    match (upper, lower) {
+        (false, false) => ' ',
+        (true, false) => glyph_char("block.upper.4"),
+        (false, true) => glyph_char("block.lower.4"),
+        (true, true) => glyph_char("block.full"),
+    }
+}
+
+/// Quadrant cell for a 2×2 pixel mask
+///
+/// Bit 0 is the upper left pixel, bit 1 upper right, bit 2 lower left and
+/// bit 3 lower right, matching the numbering of the `quad.*` glyphs.
+pub(crate) fn quadrant(mask: u8) -> char {
+    const NAMES: [&str; 16] = [
+        "",
+        "quad.1",
+        "quad.2",
+        "block.upper.4",
+        "quad.3",
+        "block.left.4",
+        "quad.2-3",
+        "quad.1-2-3",
+        "quad.4",
+        "quad.1-4",
+        "block.right.4",
+        "quad.1-2-4",
+        "block.lower.4",
+        "quad.1-3-4",
+        "quad.2-3-4",
+        "block.full",
+    ];
+    match mask & 0xF {
+        0 => ' ',
+        mask => glyph_char(NAMES[usize::from(mask)]),
+    }
+}
+
+/// Sextant cell for a 2×3 pixel mask
+///
+/// Bit `row * 2 + col` is the pixel at `row` (0–2) and `col` (0–1). Patterns
+/// come from Symbols for Legacy Computing, except the four that already exist
+/// as block elements.
+pub(crate) fn sextant(mask: u8) -> char {
+    const LEFT: u8 = 0b01_0101;
+    const RIGHT: u8 = 0b10_1010;
+    match mask & 0x3F {
+        0 => ' ',
+        LEFT => glyph_char("block.left.4"),
+        RIGHT => glyph_char("block.right.4"),
+        0x3F => glyph_char("block.full"),
+        mask => {
+            // U+1FB00 starts at mask 1 and skips the two half blocks
+            let skipped = u32::from(mask > LEFT) + u32::from(mask > RIGHT);
+            char::from_u32(0x1FB00 + u32::from(mask) - 1 - skipped).expect("sextant")
+        }
+    }
+}
+
+/// Bit of the braille dot at `row` (0–3) and `col` (0–1) of a cell
+pub(crate) const fn braille_bit(row: usize, col: usize) -> u8 {
+    // Dots 1–3 and 4–6 run down the columns; dots 7 and 8 were added below
+    const BITS: [[u8; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+    BITS[row][col]
+}
+
+/// Braille pattern cell for a mask of [`braille_bit`]s
+pub(crate) fn braille(mask: u8) -> char {
+    char::from_u32(0x2800 + u32::from(mask)).expect("braille patterns are U+2800–U+28FF")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quadrants_cover_every_mask() {
+        let cells: Vec<char> = (0..16).map(quadrant).collect();
+        assert_eq!(cells.iter().collect::<String>(), " ▘▝▀▖▌▞▛▗▚▐▜▄▙▟█");
+    }
+
+    #[test]
+    fn test_sextants() {
+        let cells: std::collections::HashSet<char> = (0..64).map(sextant).collect();
+        assert_eq!(cells.len(), 64);
+        assert_eq!(sextant(1), '\u{1FB00}');
+        assert_eq!(sextant(0b11_1110), '\u{1FB3B}');
+        assert_eq!(sextant(0b00_0011), '\u{1FB02}');
+        assert_eq!(sextant(0b01_0101), '▌');
+    }
+
+    #[test]
+    fn test_braille_dots() {
+        assert_eq!(braille(0), '\u{2800}');
+        let left: u8 = (0..4).map(|row| braille_bit(row, 0)).sum();
+        assert_eq!(braille(left), '⡇');
+        assert_eq!(braille(0xFF), '⣿');
+    }
+}
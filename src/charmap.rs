@@ -0,0 +1,424 @@
+use crate::decompose::{decompose, is_combining_mark};
+use crate::grapheme::{graphemes, is_emoji_component};
+use crate::styles::AccentPolicy;
+
+/// Output of mapping a single character
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mapped {
+    /// Replace the character with another character
+    Char(char),
+
+    /// Replace the character with a string
+    Str(&'static str),
+
+    /// Keep the character and append a combining character once at the end
+    /// of its grapheme cluster
+    Append(char),
+}
+
+impl Mapped {
+    /// Write this mapping of `c` to `out`
+    pub fn push_to(self, c: char, out: &mut String) {
+        match self {
+            Mapped::Char(mapped) => out.push(mapped),
+            Mapped::Str(mapped) => out.push_str(mapped),
+            Mapped::Append(mark) => {
+                out.push(c);
+                out.push(mark);
+            }
+        }
+    }
+}
+
+impl From<char> for Mapped {
+    fn from(c: char) -> Self {
+        Mapped::Char(c)
+    }
+}
+
+impl From<&'static str> for Mapped {
+    fn from(s: &'static str) -> Self {
+        Mapped::Str(s)
+    }
+}
+
+/// A character-by-character text transformation
+///
+/// Implemented by every built-in [`Style`](crate::Style), by `const` tables of
+/// `(char, char)` or `(char, &'static str)` pairs, and by types generated with
+/// [`char_map!`](crate::char_map). Anything implementing `CharMap` can be
+/// passed to [`style`](crate::style).
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{style, CharMap, Mapped};
+///
+/// struct Shout;
+///
+/// impl CharMap for Shout {
+///     fn map_char(&self, c: char) -> Option<Mapped> {
+///         match c {
+///             '.' => Some(Mapped::Char('!')),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// assert_eq!(style("Hi.", Shout), "Hi!");
+/// ```
+pub trait CharMap {
+    /// Map one character, or return `None` to leave it unchanged
+    fn map_char(&self, c: char) -> Option<Mapped>;
+
+    /// How accented and extended Latin letters are handled
+    ///
+    /// Defaults to [`AccentPolicy::Preserve`], leaving them untouched.
+    fn accent_policy(&self) -> AccentPolicy {
+        AccentPolicy::Preserve
+    }
+
+    /// Whether grapheme clusters are emitted in reverse order
+    ///
+    /// Used by upside-down text; defaults to `false`.
+    fn reversed(&self) -> bool {
+        false
+    }
+
+    /// Character inserted between adjacent styled grapheme clusters
+    ///
+    /// Used by regional indicator letters, which would otherwise fuse into
+    /// flags; defaults to `None`.
+    fn separator(&self) -> Option<char> {
+        None
+    }
+}
+
+impl<M: CharMap + ?Sized> CharMap for &M {
+    fn map_char(&self, c: char) -> Option<Mapped> {
+        (**self).map_char(c)
+    }
+
+    fn accent_policy(&self) -> AccentPolicy {
+        (**self).accent_policy()
+    }
+
+    fn reversed(&self) -> bool {
+        (**self).reversed()
+    }
+
+    fn separator(&self) -> Option<char> {
+        (**self).separator()
+    }
+}
+
+/// Lookup table of single-character replacements
+///
+/// ```
+/// use prettychars::style;
+///
+/// const LEET: &[(char, char)] = &[('a', '4'), ('e', '3'), ('o', '0')];
+/// assert_eq!(style("hello", LEET), "h3ll0");
+/// ```
+impl CharMap for [(char, char)] {
+    fn map_char(&self, c: char) -> Option<Mapped> {
+        self.iter()
+            .find(|(from, _)| *from == c)
+            .map(|(_, to)| Mapped::Char(*to))
+    }
+}
+
+impl<const N: usize> CharMap for [(char, char); N] {
+    fn map_char(&self, c: char) -> Option<Mapped> {
+        self[..].map_char(c)
+    }
+}
+
+/// Lookup table of multi-character replacements
+///
+/// ```
+/// use prettychars::style;
+///
+/// const ARROWS: &[(char, &str)] = &[('>', "->"), ('<', "<-")];
+/// assert_eq!(style("a > b", ARROWS), "a -> b");
+/// ```
+impl CharMap for [(char, &'static str)] {
+    fn map_char(&self, c: char) -> Option<Mapped> {
+        self.iter()
+            .find(|(from, _)| *from == c)
+            .map(|(_, to)| Mapped::Str(to))
+    }
+}
+
+impl<const N: usize> CharMap for [(char, &'static str); N] {
+    fn map_char(&self, c: char) -> Option<Mapped> {
+        self[..].map_char(c)
+    }
+}
+
+/// Define a unit struct implementing [`CharMap`] with a compiled `match`
+///
+/// Each arm maps a character pattern to a `char`, a `&'static str`, or a
+/// [`Mapped`] value. An optional leading `accents: <policy>;` line sets the
+/// [`AccentPolicy`].
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{char_map, style, Mapped};
+///
+/// char_map! {
+///     /// Leetspeak lookalikes
+///     pub struct Leet {
+///         'a' | 'A' => '4',
+///         'e' | 'E' => '3',
+///         't' | 'T' => '7',
+///         'w' | 'W' => "\\/\\/",
+///         '!' => Mapped::Append('\u{0332}'),
+///     }
+/// }
+///
+/// assert_eq!(style("Tweet!", Leet), "7\\/\\/337!\u{0332}");
+/// ```
+#[macro_export]
+macro_rules! char_map {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            accents: $policy:expr;
+            $($pat:pat => $out:expr),* $(,)?
+        }
+    ) => {
+        $crate::char_map!(@define $(#[$meta])* $vis $name; $($pat => $out),*);
+
+        impl $crate::CharMap for $name {
+            fn map_char(&self, c: char) -> Option<$crate::Mapped> {
+                $name::map(c)
+            }
+
+            fn accent_policy(&self) -> $crate::AccentPolicy {
+                $policy
+            }
+        }
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $($pat:pat => $out:expr),* $(,)?
+        }
+    ) => {
+        $crate::char_map!(@define $(#[$meta])* $vis $name; $($pat => $out),*);
+
+        impl $crate::CharMap for $name {
+            fn map_char(&self, c: char) -> Option<$crate::Mapped> {
+                $name::map(c)
+            }
+        }
+    };
+    (@define $(#[$meta:meta])* $vis:vis $name:ident; $($pat:pat => $out:expr),*) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        $vis struct $name;
+
+        impl $name {
+            fn map(c: char) -> Option<$crate::Mapped> {
+                match c {
+                    $($pat => Some($crate::Mapped::from($out)),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+/// Style every grapheme cluster of `text` with `map`
+///
+/// Clusters `map` cannot style are handed to `unmapped` with their byte offset,
+/// which decides what to write instead. Cluster order is reversed when
+/// [`CharMap::reversed`] says so, and `separator` goes between adjacent
+/// clusters `map` styled.
+pub(crate) fn style_clusters<M, F>(
+    text: &str,
+    map: &M,
+    policy: AccentPolicy,
+    separator: Option<char>,
+    mut unmapped: F,
+) -> String
+where
+    M: CharMap + ?Sized,
+    F: FnMut(usize, &str, &mut String),
+{
+    let mut out = String::with_capacity(text.len() * 4);
+    let mut joined = false;
+    let mut emit = |offset: usize, cluster: &str| {
+        let start = out.len();
+        if let Some(sep) = separator.filter(|_| joined) {
+            out.push(sep);
+        }
+        joined = push_cluster(map, cluster, policy, &mut out);
+        if !joined {
+            out.truncate(start);
+            unmapped(offset, cluster, &mut out);
+        }
+    };
+    let mut offset = 0;
+    if map.reversed() {
+        let clusters: Vec<(usize, &str)> = graphemes(text)
+            .map(|cluster| {
+                offset += cluster.len();
+                (offset - cluster.len(), cluster)
+            })
+            .collect();
+        for (offset, cluster) in clusters.into_iter().rev() {
+            emit(offset, cluster);
+        }
+    } else {
+        for cluster in graphemes(text) {
+            emit(offset, cluster);
+            offset += cluster.len();
+        }
+    }
+    out
+}
+
+/// Append a grapheme cluster styled by `map`, returning `false` if it has no mapping
+///
+/// Nothing is written when this returns `false`.
+pub(crate) fn push_cluster<M: CharMap + ?Sized>(
+    map: &M,
+    cluster: &str,
+    policy: AccentPolicy,
+    out: &mut String,
+) -> bool {
+    let mut chars = cluster.chars();
+    match chars.next() {
+        Some(base) => push_cluster_parts(map, base, chars.as_str(), policy, out),
+        None => false,
+    }
+}
+
+/// Append a cluster given as its base character and the marks that follow it
+///
+/// The base is mapped (or styled through its decomposition) and the marks are
+/// kept after it; [`Mapped::Append`] marks go once at the very end. Emoji
+/// sequences are never styled.
+pub(crate) fn push_cluster_parts<M: CharMap + ?Sized>(
+    map: &M,
+    base: char,
+    marks: &str,
+    policy: AccentPolicy,
+    out: &mut String,
+) -> bool {
+    if is_emoji_component(base) || marks.chars().any(is_emoji_component) {
+        return false;
+    }
+    match map.map_char(base) {
+        Some(Mapped::Append(mark)) => {
+            out.push(base);
+            out.push_str(marks);
+            out.push(mark);
+        }
+        Some(mapped) => {
+            mapped.push_to(base, out);
+            out.push_str(marks);
+        }
+        None => {
+            if !push_decomposed(map, base, policy, out) {
+                return false;
+            }
+            out.push_str(marks);
+        }
+    }
+    true
+}
+
+/// Style an extended Latin letter through its decomposition
+///
+/// Nothing is written when this returns `false`.
+fn push_decomposed<M: CharMap + ?Sized>(
+    map: &M,
+    c: char,
+    policy: AccentPolicy,
+    out: &mut String,
+) -> bool {
+    if policy == AccentPolicy::Preserve {
+        return false;
+    }
+    let Some(parts) = decompose(c) else {
+        return false;
+    };
+
+    let start = out.len();
+    let mut appended = Vec::new();
+    for part in parts.chars() {
+        if is_combining_mark(part) {
+            if policy == AccentPolicy::Combine {
+                out.push(part);
+            }
+        } else {
+            match map.map_char(part) {
+                Some(Mapped::Append(mark)) => {
+                    out.push(part);
+                    appended.push(mark);
+                }
+                Some(mapped) => mapped.push_to(part, out),
+                None => {
+                    out.truncate(start);
+                    return false;
+                }
+            }
+        }
+    }
+    // Letters kept as they are, like capitals in small caps, stay precomposed
+    if appended.is_empty() && out[start..] == *parts {
+        out.truncate(start);
+        out.push(c);
+    }
+    out.extend(appended);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style;
+
+    char_map! {
+        struct Runic {
+            accents: AccentPolicy::Combine;
+            'f' => 'ᚠ',
+            'u' => 'ᚢ',
+            'a'..='c' => "?",
+        }
+    }
+
+    #[test]
+    fn test_macro_table() {
+        assert_eq!(style("fuzz", Runic), "ᚠᚢzz");
+        assert_eq!(style("ú b", Runic), "ᚢ\u{0301} ?");
+    }
+
+    #[test]
+    fn test_const_array_table() {
+        const TABLE: [(char, char); 2] = [('x', '×'), ('-', '−')];
+        assert_eq!(style("2x-1", TABLE), "2×−1");
+    }
+
+    #[test]
+    fn test_failed_decomposition_writes_nothing() {
+        let mut out = String::from("ok");
+        assert!(!push_cluster(&Runic, "ñ", AccentPolicy::Combine, &mut out));
+        assert_eq!(out, "ok");
+    }
+
+    #[test]
+    fn test_appended_marks_follow_the_cluster() {
+        char_map! {
+            struct Underline {
+                'a'..='z' => Mapped::Append('\u{0332}'),
+            }
+        }
+        assert_eq!(style("a\u{0301}b", Underline), "a\u{0301}\u{0332}b\u{0332}");
+        assert_eq!(style("👍🏽", Underline), "👍🏽");
+    }
+}
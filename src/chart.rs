@@ -0,0 +1,663 @@
+use crate::cells::glyph_char;
+use crate::metrics::TextMetrics;
+use crate::styles::{style, Style};
+
+/// Direction the bars of a [`BarChart`] grow in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Orientation {
+    /// Bars grow rightwards from the axis, labels on the left
+    #[default]
+    Horizontal,
+
+    /// Columns grow upwards from the axis, labels below
+    Vertical,
+}
+
+/// Bar chart of labelled values, drawn with block elements
+///
+/// Bars are drawn to an eighth of a cell with `block.left.1`…`block.left.7`
+/// (or `block.lower.*` in vertical charts). Negative values extend the other
+/// way from a zero axis; Unicode has only eighth and half blocks aligned to
+/// that side (`block.right.1` and `block.right.4`, or `block.upper.*`), so
+/// negative bars round to the nearest of those.
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::BarChart;
+///
+/// let chart = BarChart::new()
+///     .bar("rust", 8.0)
+///     .bar("go", 3.0)
+///     .size(4)
+///     .render();
+/// assert_eq!(
+///     chart,
+///     "rust │████ 8\n\
+///      go   │█▌ 3\n     \
+///           └────\n     \
+///           0   8"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BarChart {
+    bars: Vec<(String, f64)>,
+    orientation: Orientation,
+    size: Option<usize>,
+    label_style: Option<Style>,
+    hide_values: bool,
+    precision: Option<usize>,
+}
+
+impl BarChart {
+    /// Empty horizontal chart
+    pub fn new() -> Self {
+        BarChart::default()
+    }
+
+    /// Add a bar
+    pub fn bar(mut self, label: impl Into<String>, value: f64) -> Self {
+        self.bars.push((label.into(), value));
+        self
+    }
+
+    /// Add a bar for each `(label, value)` pair
+    pub fn bars<L: Into<String>>(mut self, bars: impl IntoIterator<Item = (L, f64)>) -> Self {
+        self.bars
+            .extend(bars.into_iter().map(|(label, value)| (label.into(), value)));
+        self
+    }
+
+    /// Draw horizontal bars or vertical columns
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Cells between the lowest and highest value: 40 columns across by
+    /// default, or 10 lines up
+    ///
+    /// A chart with both negative and positive values is at least 2 cells.
+    pub fn size(mut self, cells: usize) -> Self {
+        self.size = Some(cells);
+        self
+    }
+
+    /// Style the bar labels
+    pub fn label_style(mut self, style: Style) -> Self {
+        self.label_style = Some(style);
+        self
+    }
+
+    /// Write each value at the end of its bar; on by default
+    pub fn values(mut self, show: bool) -> Self {
+        self.hide_values = !show;
+        self
+    }
+
+    /// Digits after the decimal point in values and axis labels
+    ///
+    /// By default values are written in full, so `2.5` stays `2.5` and `3.0`
+    /// becomes `3`.
+    pub fn precision(mut self, digits: usize) -> Self {
+        self.precision = Some(digits);
+        self
+    }
+
+    /// Draw the chart, or an empty string if it has no bars
+    ///
+    /// Non-finite values are drawn as empty bars.
+    pub fn render(&self) -> String {
+        if self.bars.is_empty() {
+            return String::new();
+        }
+        match self.orientation {
+            Orientation::Horizontal => self.render_horizontal(self.size.unwrap_or(40)),
+            Orientation::Vertical => self.render_vertical(self.size.unwrap_or(10)),
+        }
+    }
+
+    fn format(&self, value: f64) -> String {
+        match self.precision {
+            Some(digits) => format!("{value:.digits$}"),
+            None => format!("{value}"),
+        }
+    }
+
+    fn labels(&self) -> Vec<String> {
+        self.bars
+            .iter()
+            .map(|(label, _)| match self.label_style {
+                Some(s) => style(label, s),
+                None => label.clone(),
+            })
+            .collect()
+    }
+
+    fn render_horizontal(&self, size: usize) -> String {
+        let scale = Scale::new(self.bars.iter().map(|&(_, v)| v), size);
+        let labels = self.labels();
+        let label_width = labels.iter().map(|l| columns(l)).max().unwrap_or(0);
+        let full = glyph_char("block.full");
+
+        let mut lines = Vec::with_capacity(self.bars.len() + 2);
+        for (label, &(_, value)) in labels.iter().zip(&self.bars) {
+            let mut line = pad(label, label_width);
+            line.push(' ');
+            let eighths = scale.eighths(value);
+            let mut negative = String::new();
+            if value < 0.0 {
+                let eighths = round_negative(eighths);
+                if eighths % 8 > 0 {
+                    negative.push(glyph_char(&format!("block.right.{}", eighths % 8)));
+                }
+                negative.extend(std::iter::repeat(full).take(eighths / 8));
+            }
+            let padding = scale.negative - negative.chars().count();
+            line.extend(std::iter::repeat(' ').take(padding));
+            line.push_str(&negative);
+            line.push(glyph_char("box.light.v"));
+            if value > 0.0 {
+                line.extend(std::iter::repeat(full).take(eighths / 8));
+                if eighths % 8 > 0 {
+                    line.push(glyph_char(&format!("block.left.{}", eighths % 8)));
+                }
+            }
+            if !self.hide_values {
+                line.push(' ');
+                line.push_str(&self.format(value));
+            }
+            lines.push(line.trim_end().to_string());
+        }
+
+        let indent = " ".repeat(label_width + 1);
+        let rule = glyph_char("box.light.h");
+        let mut axis = indent.clone();
+        axis.extend(std::iter::repeat(rule).take(scale.negative));
+        axis.push(glyph_char(if scale.negative > 0 {
+            "box.light.t-up"
+        } else {
+            "box.light.bl"
+        }));
+        axis.extend(std::iter::repeat(rule).take(scale.positive));
+        lines.push(axis);
+
+        // Zero, then the extreme values under the ends of the longest bars
+        let mut ticks = vec![' '; scale.negative + 1 + scale.positive];
+        let mut place = |text: &str, start: usize| {
+            let end = start + text.len();
+            let clear = end <= ticks.len()
+                && ticks[start.saturating_sub(1)..(end + 1).min(ticks.len())]
+                    .iter()
+                    .all(|&c| c == ' ');
+            if clear {
+                for (slot, c) in ticks[start..end].iter_mut().zip(text.chars()) {
+                    *slot = c;
+                }
+            }
+        };
+        place("0", scale.negative);
+        if scale.positive > 0 {
+            let high = self.format(scale.high);
+            place(
+                &high,
+                (scale.negative + 1 + scale.cells(scale.high)).saturating_sub(high.len()),
+            );
+        }
+        if scale.negative > 0 {
+            place(
+                &self.format(scale.low),
+                scale.negative - scale.cells(scale.low),
+            );
+        }
+        let ticks: String = ticks.into_iter().collect();
+        lines.push(format!("{indent}{}", ticks.trim_end()));
+        lines.join("\n")
+    }
+
+    fn render_vertical(&self, size: usize) -> String {
+        let scale = Scale::new(self.bars.iter().map(|&(_, v)| v), size);
+        let labels = self.labels();
+        let values: Vec<String> = self.bars.iter().map(|&(_, v)| self.format(v)).collect();
+        let width = labels
+            .iter()
+            .map(|l| columns(l))
+            .chain(values.iter().filter(|_| !self.hide_values).map(|v| v.len()))
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        // A row for value labels past the bars, on each side that has one
+        let above = self.bars.iter().any(|&(_, v)| v >= 0.0 || v.is_nan());
+        let below = self.bars.iter().any(|&(_, v)| v < 0.0);
+        let pad_above = usize::from(!self.hide_values && above);
+        let pad_below = usize::from(!self.hide_values && below);
+
+        let high = self.format(scale.high);
+        let low = self.format(scale.low);
+        let gutter = [high.len(), 1, low.len()].into_iter().max().unwrap_or(1);
+        let row = |tick: &str, edge: &str, cells: Vec<String>, join: &str| {
+            let edge = if edge.is_empty() {
+                ' '
+            } else {
+                glyph_char(edge)
+            };
+            let line = format!("{tick:>gutter$} {edge}{}", cells.join(join));
+            line.trim_end().to_string()
+        };
+        let full = glyph_char("block.full").to_string();
+        let blank = " ".repeat(width);
+        let value_cell = |i: usize| pad(&values[i], width);
+
+        let mut lines = Vec::new();
+        // Above the axis, from the top down; `h` counts cells up from the axis
+        for h in (1..=scale.positive + pad_above).rev() {
+            let cells = (0..self.bars.len())
+                .map(|i| {
+                    let value = self.bars[i].1;
+                    // NaN has no bar, but its label goes above the axis
+                    let above = value >= 0.0 || value.is_nan();
+                    let eighths = if value > 0.0 { scale.eighths(value) } else { 0 };
+                    let (whole, part) = (eighths / 8, eighths % 8);
+                    if above && h <= whole {
+                        full.repeat(width)
+                    } else if value > 0.0 && h == whole + 1 && part > 0 {
+                        glyph_char(&format!("block.lower.{part}"))
+                            .to_string()
+                            .repeat(width)
+                    } else if above && !self.hide_values && h == whole + 1 + usize::from(part > 0) {
+                        value_cell(i)
+                    } else {
+                        blank.clone()
+                    }
+                })
+                .collect();
+            let (tick, edge) = match h {
+                h if h > scale.positive => ("", ""),
+                h if h == scale.cells(scale.high) => (high.as_str(), "box.light.t-left"),
+                _ => ("", "box.light.v"),
+            };
+            lines.push(row(tick, edge, cells, " "));
+        }
+
+        let rule = glyph_char("box.light.h").to_string();
+        let axis = vec![rule.repeat(width); self.bars.len()];
+        let corner = if scale.negative > 0 {
+            "box.light.cross"
+        } else {
+            "box.light.bl"
+        };
+        lines.push(row("0", corner, axis, &rule));
+
+        // Below the axis; `d` counts cells down from the axis
+        for d in 1..=scale.negative + pad_below {
+            let cells = (0..self.bars.len())
+                .map(|i| {
+                    let value = self.bars[i].1;
+                    if value >= 0.0 || value.is_nan() {
+                        return blank.clone();
+                    }
+                    let eighths = round_negative(scale.eighths(value));
+                    let (whole, part) = (eighths / 8, eighths % 8);
+                    if d <= whole {
+                        full.repeat(width)
+                    } else if d == whole + 1 && part > 0 {
+                        glyph_char(&format!("block.upper.{part}"))
+                            .to_string()
+                            .repeat(width)
+                    } else if !self.hide_values && d == whole + 1 + usize::from(part > 0) {
+                        value_cell(i)
+                    } else {
+                        blank.clone()
+                    }
+                })
+                .collect();
+            let (tick, edge) = match d {
+                d if d > scale.negative => ("", ""),
+                d if d == scale.cells(scale.low) => (low.as_str(), "box.light.t-left"),
+                _ => ("", "box.light.v"),
+            };
+            lines.push(row(tick, edge, cells, " "));
+        }
+
+        let labels = labels.iter().map(|l| pad(l, width)).collect();
+        lines.push(row("", "", labels, " "));
+        lines.join("\n")
+    }
+}
+
+/// How values map to cells on each side of the zero axis
+struct Scale {
+    /// Cells left of (or below) the axis
+    negative: usize,
+    /// Cells right of (or above) the axis
+    positive: usize,
+    /// Value of one cell, 0 when every value is 0
+    per_cell: f64,
+    low: f64,
+    high: f64,
+}
+
+impl Scale {
+    fn new(values: impl Iterator<Item = f64>, size: usize) -> Self {
+        let (mut low, mut high) = (0.0_f64, 0.0_f64);
+        for value in values.filter(|v| v.is_finite()) {
+            low = low.min(value);
+            high = high.max(value);
+        }
+        // Each side with values gets a cell, even past `size`
+        let size = size.max(usize::from(low < 0.0) + usize::from(high > 0.0));
+        // The side that needs it most sets the scale, so both extremes fit
+        let per_cell = |negative: usize| {
+            let per_side = |extreme: f64, cells: usize| match cells {
+                0 => 0.0,
+                cells => extreme / cells as f64,
+            };
+            per_side(high, size - negative).max(per_side(-low, negative))
+        };
+        // Split the cells between the sides for the largest scale
+        let negative = match (low < 0.0, high > 0.0) {
+            (false, _) => 0,
+            (true, false) => size,
+            (true, true) => (1..size)
+                .min_by(|&a, &b| per_cell(a).total_cmp(&per_cell(b)))
+                .unwrap_or(0),
+        };
+        let positive = size - negative;
+        Scale {
+            negative,
+            positive,
+            per_cell: per_cell(negative),
+            low,
+            high,
+        }
+    }
+
+    /// Cells the bar for `value` reaches into
+    fn cells(&self, value: f64) -> usize {
+        (self.eighths(value) + 7) / 8
+    }
+
+    /// Length of the bar for `value` in eighths of a cell, within its side
+    fn eighths(&self, value: f64) -> usize {
+        if !value.is_finite() || self.per_cell == 0.0 {
+            return 0;
+        }
+        let cells = if value < 0.0 {
+            self.negative
+        } else {
+            self.positive
+        };
+        ((value.abs() / self.per_cell * 8.0).round() as usize).min(cells * 8)
+    }
+}
+
+/// Round a negative bar to the partial cells that exist: an eighth or a half
+fn round_negative(eighths: usize) -> usize {
+    let part = match eighths % 8 {
+        0 => 0,
+        1 | 2 => 1,
+        3..=6 => 4,
+        _ => 8,
+    };
+    eighths / 8 * 8 + part
+}
+
+fn columns(text: &str) -> usize {
+    TextMetrics::of(text).columns
+}
+
+/// `text` followed by spaces up to `width` columns
+fn pad(text: &str, width: usize) -> String {
+    let mut padded = text.to_string();
+    padded.extend(std::iter::repeat(' ').take(width.saturating_sub(columns(text))));
+    padded
+}
+
+/// Histogram of raw samples, counted into equal-width bins
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::Histogram;
+///
+/// let chart = Histogram::new([1.0, 2.0, 2.5, 3.0, 9.0])
+///     .bins(2)
+///     .chart()
+///     .size(4)
+///     .render();
+/// assert!(chart.starts_with("1–5 │████ 4\n5–9 │█ 1"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Histogram {
+    samples: Vec<f64>,
+    bins: Option<usize>,
+    range: Option<(f64, f64)>,
+}
+
+impl Histogram {
+    /// Histogram of `samples`; non-finite samples are ignored
+    pub fn new(samples: impl IntoIterator<Item = f64>) -> Self {
+        Histogram {
+            samples: samples.into_iter().filter(|s| s.is_finite()).collect(),
+            bins: None,
+            range: None,
+        }
+    }
+
+    /// Number of bins, 10 by default
+    pub fn bins(mut self, bins: usize) -> Self {
+        self.bins = Some(bins);
+        self
+    }
+
+    /// Count samples from `min` to `max` only, instead of the samples' range
+    pub fn range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Sample counts per bin
+    ///
+    /// Each bin holds samples from its lower edge up to, but not including,
+    /// its upper edge; the last bin also holds the maximum. Every count is 0
+    /// when the range is empty or its width overflows `f64`.
+    pub fn counts(&self) -> Vec<usize> {
+        let bins = self.bins.unwrap_or(10);
+        let mut counts = vec![0; bins];
+        let Some((min, max)) = self.bounds() else {
+            return counts;
+        };
+        for &sample in &self.samples {
+            if bins == 0 || sample < min || sample > max {
+                continue;
+            }
+            let bin = ((sample - min) / (max - min) * bins as f64) as usize;
+            counts[bin.min(bins - 1)] += 1;
+        }
+        counts
+    }
+
+    /// A [`BarChart`] of the counts, labelled with the bin edges
+    pub fn chart(&self) -> BarChart {
+        let Some((min, max)) = self.bounds() else {
+            return BarChart::new();
+        };
+        let counts = self.counts();
+        let bins = counts.len();
+        let edges: Vec<f64> = (0..=bins)
+            .map(|i| min + (max - min) * (i as f64 / bins as f64))
+            .collect();
+        // Fewest decimals that still tell the edges apart exactly
+        let digits = (0..=6)
+            .find(|&d| {
+                let scale = 10_f64.powi(d);
+                edges
+                    .iter()
+                    .all(|e| ((e * scale).round() - e * scale).abs() < 1e-6)
+            })
+            .unwrap_or(6) as usize;
+        BarChart::new().bars(counts.iter().enumerate().map(|(i, &count)| {
+            let label = format!("{:.digits$}–{:.digits$}", edges[i], edges[i + 1]);
+            (label, count as f64)
+        }))
+    }
+
+    /// Draw the histogram with the default chart settings
+    pub fn render(&self) -> String {
+        self.chart().render()
+    }
+
+    fn bounds(&self) -> Option<(f64, f64)> {
+        let (min, max) = match self.range {
+            Some(range) => range,
+            None => {
+                let min = self.samples.iter().copied().reduce(f64::min)?;
+                let max = self.samples.iter().copied().reduce(f64::max)?;
+                if min < max {
+                    (min, max)
+                } else {
+                    (min - 0.5, max + 0.5)
+                }
+            }
+        };
+        // An infinite width would put every sample in the first bin
+        Some((min, max)).filter(|(min, max)| min < max && (max - min).is_finite())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eighth_precision() {
+        let chart = BarChart::new()
+            .bars((1..=8).map(|n| (n.to_string(), n as f64)))
+            .size(1)
+            .values(false)
+            .render();
+        let bars: Vec<String> = chart
+            .lines()
+            .take(8)
+            .map(|l| l.chars().skip(3).collect())
+            .collect();
+        assert_eq!(bars, ["▏", "▎", "▍", "▌", "▋", "▊", "▉", "█"]);
+    }
+
+    #[test]
+    fn test_negative_values() {
+        let chart = BarChart::new()
+            .bar("loss", -2.0)
+            .bar("tiny", -0.25)
+            .bar("gain", 4.0)
+            .size(9)
+            .render();
+        assert_eq!(
+            chart,
+            "loss ███│ -2\n\
+             tiny   ▐│ -0.25\n\
+             gain    │██████ 4\n     \
+             ───┴──────\n     \
+             -2 0     4"
+        );
+        // Tick labels that would touch are left out
+        let cramped = BarChart::new()
+            .bar("a", -2.0)
+            .bar("b", 4.0)
+            .size(6)
+            .render();
+        assert!(cramped.ends_with("\n    0   4"));
+        // Both sides keep a cell, even with room for only one
+        let tiny = BarChart::new()
+            .bar("a", -1.0)
+            .bar("b", 1.0)
+            .size(1)
+            .render();
+        assert_eq!(tiny, "a █│ -1\nb  │█ 1\n  ─┴─\n   0");
+    }
+
+    #[test]
+    fn test_vertical() {
+        let chart = BarChart::new()
+            .bars([("a", 2.0), ("b", 1.5), ("c", -1.0)])
+            .orientation(Orientation::Vertical)
+            .size(3)
+            .render();
+        assert_eq!(
+            chart,
+            "    2   1.5\n \
+             2 ┤███ ▄▄▄\n   \
+             │███ ███\n \
+             0 ┼───────────\n\
+             -1 ┤        ███\n            \
+             -1\n    \
+             a   b   c"
+        );
+        // A NaN bar has no height, and its label stays above the axis
+        let chart = BarChart::new()
+            .bars([("a", -1.0), ("b", f64::NAN)])
+            .orientation(Orientation::Vertical)
+            .size(1)
+            .render();
+        assert_eq!(
+            chart,
+            "        NaN\n \
+             0 ┼───────\n\
+             -1 ┤███\n    \
+             -1\n    \
+             a   b"
+        );
+        // Label rows only on the sides that have labels
+        let negative = BarChart::new()
+            .bars([("a", -2.0), ("b", -1.0)])
+            .orientation(Orientation::Vertical)
+            .size(2)
+            .render();
+        assert_eq!(
+            negative,
+            " 0 ┼─────\n   │██ ██\n-2 ┤██ -1\n    -2\n    a  b"
+        );
+        let infinite = BarChart::new()
+            .bars([("a", 2.0), ("b", f64::NEG_INFINITY)])
+            .orientation(Orientation::Vertical)
+            .size(2)
+            .render();
+        assert_eq!(
+            infinite,
+            "   2\n2 ┤████\n  │████\n0 └─────────\n        -inf\n   a    b"
+        );
+    }
+
+    #[test]
+    fn test_label_style_and_precision() {
+        let chart = BarChart::new()
+            .bar("ab", 1.0 / 3.0)
+            .label_style(Style::Fullwidth)
+            .precision(2)
+            .size(6)
+            .render();
+        assert_eq!(chart, "ａｂ │██████ 0.33\n     └──────\n     0  0.33");
+        assert_eq!(BarChart::new().render(), "");
+    }
+
+    #[test]
+    fn test_histogram() {
+        let samples = [0.0, 0.1, 0.2, 0.25, 0.5, 0.9, 1.0];
+        let histogram = Histogram::new(samples).bins(4);
+        assert_eq!(histogram.counts(), [3, 1, 1, 2]);
+        let chart = histogram.chart().values(false).size(3).render();
+        assert!(chart.starts_with("0.00–0.25 │███\n0.25–0.50 │█\n"));
+        assert_eq!(Histogram::new([5.0, 5.0]).bins(1).counts(), [2]);
+        assert_eq!(
+            Histogram::new([1.0, 9.0]).range(0.0, 2.0).bins(2).counts(),
+            [0, 1]
+        );
+        assert_eq!(Histogram::new([]).render(), "");
+        let infinite = Histogram::new([1.0]).range(0.0, f64::INFINITY);
+        assert_eq!(infinite.counts(), [0; 10]);
+        assert_eq!(infinite.render(), "");
+        let wide = Histogram::new([-f64::MAX, f64::MAX]);
+        assert_eq!(wide.counts(), [0; 10]);
+        assert_eq!(wide.render(), "");
+    }
+}
@@ -0,0 +1,265 @@
+use crate::grapheme::{graphemes, is_emoji_component};
+use crate::styles::{style, Style};
+use std::fmt;
+
+/// Typeface family of a composed style
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Family {
+    /// Serif (plain text when neither bold nor italic)
+    Serif,
+
+    /// Sans-serif (𝖠𝖡𝖢𝖺𝖻𝖼)
+    SansSerif,
+
+    /// Script / Calligraphic (𝒜𝐵𝒞𝒶𝒷𝒸)
+    Script,
+
+    /// Fraktur / Gothic (𝔄𝔅𝔆𝔞𝔟𝔠)
+    Fraktur,
+
+    /// Double-struck / Blackboard bold (𝔸𝔹ℂ𝕒𝕓𝕔)
+    DoubleStruck,
+
+    /// Monospace (𝙰𝙱𝙲𝚊𝚋𝚌)
+    Monospace,
+}
+
+/// Combining effect layered on top of a styled character
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Effect {
+    /// Long stroke overlay, U+0336 (A̶)
+    Strikethrough,
+
+    /// Low line, U+0332 (A̲)
+    Underline,
+
+    /// Double low line, U+0333 (A̳)
+    DoubleUnderline,
+
+    /// Overline, U+0305 (A̅)
+    Overline,
+
+    /// Long solidus overlay, U+0338 (A̸)
+    Slash,
+}
+
+impl Effect {
+    /// The combining character appended for this effect
+    pub const fn mark(self) -> char {
+        match self {
+            Effect::Strikethrough => '\u{0336}',
+            Effect::Underline => '\u{0332}',
+            Effect::DoubleUnderline => '\u{0333}',
+            Effect::Overline => '\u{0305}',
+            Effect::Slash => '\u{0338}',
+        }
+    }
+}
+
+/// Error returned when a family/weight/slant combination has no Unicode variant
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedStyle {
+    /// Requested letterform family
+    pub family: Family,
+    /// Whether bold was requested
+    pub bold: bool,
+    /// Whether italic was requested
+    pub italic: bool,
+}
+
+impl fmt::Display for UnsupportedStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let weight = match (self.bold, self.italic) {
+            (true, true) => "bold italic",
+            (true, false) => "bold",
+            (false, true) => "italic",
+            (false, false) => "regular",
+        };
+        write!(
+            f,
+            "Unsupported style: {:?} has no {} variant",
+            self.family, weight
+        )
+    }
+}
+
+impl std::error::Error for UnsupportedStyle {}
+
+/// A composed style: family, weight, slant and combining effects
+///
+/// Resolves to the matching Mathematical Alphanumeric [`Style`] and layers
+/// any effects on top of each styled character.
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{Effect, Family, StyleSpec};
+///
+/// let spec = StyleSpec::new(Family::SansSerif).bold().italic();
+/// assert_eq!(spec.apply("Hi").unwrap(), "𝙃𝙞");
+///
+/// let spec = StyleSpec::new(Family::Serif).bold().effect(Effect::Underline);
+/// assert_eq!(spec.apply("Hi").unwrap(), "𝐇\u{0332}𝐢\u{0332}");
+/// ```
+///
+/// Combinations without a Unicode variant are rejected:
+///
+/// ```
+/// use prettychars::{Family, StyleSpec};
+///
+/// assert!(StyleSpec::new(Family::Fraktur).italic().apply("Hi").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StyleSpec {
+    /// Letterform family
+    pub family: Family,
+    /// Bold weight
+    pub bold: bool,
+    /// Italic slant
+    pub italic: bool,
+    /// Combining effects, applied in order
+    pub effects: Vec<Effect>,
+}
+
+impl StyleSpec {
+    /// Start a regular-weight, upright spec for the given family
+    pub fn new(family: Family) -> Self {
+        StyleSpec {
+            family,
+            bold: false,
+            italic: false,
+            effects: Vec::new(),
+        }
+    }
+
+    /// Use the bold weight
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    /// Use the italic slant
+    pub fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    /// Layer a combining effect (applied in the order added)
+    pub fn effect(mut self, effect: Effect) -> Self {
+        self.effects.push(effect);
+        self
+    }
+
+    /// Resolve family, weight and slant to a [`Style`]
+    ///
+    /// Returns `Ok(None)` for regular upright serif, which is plain text.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnsupportedStyle`] if Unicode has no such variant (e.g. Fraktur italic).
+    pub fn resolve(&self) -> Result<Option<Style>, UnsupportedStyle> {
+        use Family::*;
+        let resolved = match (self.family, self.bold, self.italic) {
+            (Serif, false, false) => None,
+            (Serif, true, false) => Some(Style::MathBold),
+            (Serif, false, true) => Some(Style::Italic),
+            (Serif, true, true) => Some(Style::BoldItalic),
+            (SansSerif, false, false) => Some(Style::SansSerif),
+            (SansSerif, true, false) => Some(Style::SansSerifBold),
+            (SansSerif, false, true) => Some(Style::SansSerifItalic),
+            (SansSerif, true, true) => Some(Style::SansSerifBoldItalic),
+            (Script, false, false) => Some(Style::Script),
+            (Script, true, false) => Some(Style::ScriptBold),
+            (Fraktur, false, false) => Some(Style::Fraktur),
+            (Fraktur, true, false) => Some(Style::FrakturBold),
+            (DoubleStruck, false, false) => Some(Style::DoubleStruck),
+            (Monospace, false, false) => Some(Style::Monospace),
+            _ => {
+                return Err(UnsupportedStyle {
+                    family: self.family,
+                    bold: self.bold,
+                    italic: self.italic,
+                })
+            }
+        };
+        Ok(resolved)
+    }
+
+    /// Apply this spec to text
+    ///
+    /// Effects are appended once to every grapheme cluster after styling;
+    /// whitespace and emoji sequences are left alone.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnsupportedStyle`] if the spec cannot be resolved.
+    pub fn apply(&self, text: &str) -> Result<String, UnsupportedStyle> {
+        let styled = match self.resolve()? {
+            Some(s) => style(text, s),
+            None => text.to_string(),
+        };
+        Ok(apply_effects(&styled, &self.effects))
+    }
+}
+
+/// Append effect marks once to every grapheme cluster
+///
+/// Whitespace and emoji sequences are left alone.
+pub(crate) fn apply_effects(text: &str, effects: &[Effect]) -> String {
+    if effects.is_empty() {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len() * (1 + effects.len()));
+    for cluster in graphemes(text) {
+        out.push_str(cluster);
+        let skip =
+            cluster.chars().all(char::is_whitespace) || cluster.chars().any(is_emoji_component);
+        if !skip {
+            out.extend(effects.iter().map(|e| e.mark()));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_sans_serif_bold_italic() {
+        let spec = StyleSpec::new(Family::SansSerif).bold().italic();
+        assert_eq!(spec.resolve(), Ok(Some(Style::SansSerifBoldItalic)));
+    }
+
+    #[test]
+    fn test_plain_serif_is_identity() {
+        assert_eq!(StyleSpec::new(Family::Serif).apply("Hi").unwrap(), "Hi");
+    }
+
+    #[test]
+    fn test_impossible_combination() {
+        let err = StyleSpec::new(Family::Fraktur)
+            .italic()
+            .resolve()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unsupported style: Fraktur has no italic variant"
+        );
+        assert!(StyleSpec::new(Family::Monospace).bold().resolve().is_err());
+    }
+
+    #[test]
+    fn test_effects_layered_in_order() {
+        let spec = StyleSpec::new(Family::SansSerif)
+            .bold()
+            .effect(Effect::Underline)
+            .effect(Effect::Strikethrough);
+        assert_eq!(
+            spec.apply("A b").unwrap(),
+            "𝗔\u{0332}\u{0336} 𝗯\u{0332}\u{0336}"
+        );
+    }
+}
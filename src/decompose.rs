@@ -0,0 +1,327 @@
+use phf::phf_map;
+
+/// Canonical decompositions (NFD) of precomposed letters in Latin-1 Supplement,
+/// Latin Extended-A and Latin Extended-B
+///
+/// Each entry is an ASCII base letter followed by its combining marks. Letters
+/// whose NFD base has no decomposition of its own (e.g. Ǣ, Ǿ) are in
+/// [`SUPPLEMENTARY`] instead.
+static CANONICAL: phf::Map<char, &'static str> = phf_map! {
+    'À' => "A\u{0300}",
+    'Á' => "A\u{0301}",
+    'Â' => "A\u{0302}",
+    'Ã' => "A\u{0303}",
+    'Ä' => "A\u{0308}",
+    'Å' => "A\u{030A}",
+    'Ç' => "C\u{0327}",
+    'È' => "E\u{0300}",
+    'É' => "E\u{0301}",
+    'Ê' => "E\u{0302}",
+    'Ë' => "E\u{0308}",
+    'Ì' => "I\u{0300}",
+    'Í' => "I\u{0301}",
+    'Î' => "I\u{0302}",
+    'Ï' => "I\u{0308}",
+    'Ñ' => "N\u{0303}",
+    'Ò' => "O\u{0300}",
+    'Ó' => "O\u{0301}",
+    'Ô' => "O\u{0302}",
+    'Õ' => "O\u{0303}",
+    'Ö' => "O\u{0308}",
+    'Ù' => "U\u{0300}",
+    'Ú' => "U\u{0301}",
+    'Û' => "U\u{0302}",
+    'Ü' => "U\u{0308}",
+    'Ý' => "Y\u{0301}",
+    'à' => "a\u{0300}",
+    'á' => "a\u{0301}",
+    'â' => "a\u{0302}",
+    'ã' => "a\u{0303}",
+    'ä' => "a\u{0308}",
+    'å' => "a\u{030A}",
+    'ç' => "c\u{0327}",
+    'è' => "e\u{0300}",
+    'é' => "e\u{0301}",
+    'ê' => "e\u{0302}",
+    'ë' => "e\u{0308}",
+    'ì' => "i\u{0300}",
+    'í' => "i\u{0301}",
+    'î' => "i\u{0302}",
+    'ï' => "i\u{0308}",
+    'ñ' => "n\u{0303}",
+    'ò' => "o\u{0300}",
+    'ó' => "o\u{0301}",
+    'ô' => "o\u{0302}",
+    'õ' => "o\u{0303}",
+    'ö' => "o\u{0308}",
+    'ù' => "u\u{0300}",
+    'ú' => "u\u{0301}",
+    'û' => "u\u{0302}",
+    'ü' => "u\u{0308}",
+    'ý' => "y\u{0301}",
+    'ÿ' => "y\u{0308}",
+    'Ā' => "A\u{0304}",
+    'ā' => "a\u{0304}",
+    'Ă' => "A\u{0306}",
+    'ă' => "a\u{0306}",
+    'Ą' => "A\u{0328}",
+    'ą' => "a\u{0328}",
+    'Ć' => "C\u{0301}",
+    'ć' => "c\u{0301}",
+    'Ĉ' => "C\u{0302}",
+    'ĉ' => "c\u{0302}",
+    'Ċ' => "C\u{0307}",
+    'ċ' => "c\u{0307}",
+    'Č' => "C\u{030C}",
+    'č' => "c\u{030C}",
+    'Ď' => "D\u{030C}",
+    'ď' => "d\u{030C}",
+    'Ē' => "E\u{0304}",
+    'ē' => "e\u{0304}",
+    'Ĕ' => "E\u{0306}",
+    'ĕ' => "e\u{0306}",
+    'Ė' => "E\u{0307}",
+    'ė' => "e\u{0307}",
+    'Ę' => "E\u{0328}",
+    'ę' => "e\u{0328}",
+    'Ě' => "E\u{030C}",
+    'ě' => "e\u{030C}",
+    'Ĝ' => "G\u{0302}",
+    'ĝ' => "g\u{0302}",
+    'Ğ' => "G\u{0306}",
+    'ğ' => "g\u{0306}",
+    'Ġ' => "G\u{0307}",
+    'ġ' => "g\u{0307}",
+    'Ģ' => "G\u{0327}",
+    'ģ' => "g\u{0327}",
+    'Ĥ' => "H\u{0302}",
+    'ĥ' => "h\u{0302}",
+    'Ĩ' => "I\u{0303}",
+    'ĩ' => "i\u{0303}",
+    'Ī' => "I\u{0304}",
+    'ī' => "i\u{0304}",
+    'Ĭ' => "I\u{0306}",
+    'ĭ' => "i\u{0306}",
+    'Į' => "I\u{0328}",
+    'į' => "i\u{0328}",
+    'İ' => "I\u{0307}",
+    'Ĵ' => "J\u{0302}",
+    'ĵ' => "j\u{0302}",
+    'Ķ' => "K\u{0327}",
+    'ķ' => "k\u{0327}",
+    'Ĺ' => "L\u{0301}",
+    'ĺ' => "l\u{0301}",
+    'Ļ' => "L\u{0327}",
+    'ļ' => "l\u{0327}",
+    'Ľ' => "L\u{030C}",
+    'ľ' => "l\u{030C}",
+    'Ń' => "N\u{0301}",
+    'ń' => "n\u{0301}",
+    'Ņ' => "N\u{0327}",
+    'ņ' => "n\u{0327}",
+    'Ň' => "N\u{030C}",
+    'ň' => "n\u{030C}",
+    'Ō' => "O\u{0304}",
+    'ō' => "o\u{0304}",
+    'Ŏ' => "O\u{0306}",
+    'ŏ' => "o\u{0306}",
+    'Ő' => "O\u{030B}",
+    'ő' => "o\u{030B}",
+    'Ŕ' => "R\u{0301}",
+    'ŕ' => "r\u{0301}",
+    'Ŗ' => "R\u{0327}",
+    'ŗ' => "r\u{0327}",
+    'Ř' => "R\u{030C}",
+    'ř' => "r\u{030C}",
+    'Ś' => "S\u{0301}",
+    'ś' => "s\u{0301}",
+    'Ŝ' => "S\u{0302}",
+    'ŝ' => "s\u{0302}",
+    'Ş' => "S\u{0327}",
+    'ş' => "s\u{0327}",
+    'Š' => "S\u{030C}",
+    'š' => "s\u{030C}",
+    'Ţ' => "T\u{0327}",
+    'ţ' => "t\u{0327}",
+    'Ť' => "T\u{030C}",
+    'ť' => "t\u{030C}",
+    'Ũ' => "U\u{0303}",
+    'ũ' => "u\u{0303}",
+    'Ū' => "U\u{0304}",
+    'ū' => "u\u{0304}",
+    'Ŭ' => "U\u{0306}",
+    'ŭ' => "u\u{0306}",
+    'Ů' => "U\u{030A}",
+    'ů' => "u\u{030A}",
+    'Ű' => "U\u{030B}",
+    'ű' => "u\u{030B}",
+    'Ų' => "U\u{0328}",
+    'ų' => "u\u{0328}",
+    'Ŵ' => "W\u{0302}",
+    'ŵ' => "w\u{0302}",
+    'Ŷ' => "Y\u{0302}",
+    'ŷ' => "y\u{0302}",
+    'Ÿ' => "Y\u{0308}",
+    'Ź' => "Z\u{0301}",
+    'ź' => "z\u{0301}",
+    'Ż' => "Z\u{0307}",
+    'ż' => "z\u{0307}",
+    'Ž' => "Z\u{030C}",
+    'ž' => "z\u{030C}",
+    'Ơ' => "O\u{031B}",
+    'ơ' => "o\u{031B}",
+    'Ư' => "U\u{031B}",
+    'ư' => "u\u{031B}",
+    'Ǎ' => "A\u{030C}",
+    'ǎ' => "a\u{030C}",
+    'Ǐ' => "I\u{030C}",
+    'ǐ' => "i\u{030C}",
+    'Ǒ' => "O\u{030C}",
+    'ǒ' => "o\u{030C}",
+    'Ǔ' => "U\u{030C}",
+    'ǔ' => "u\u{030C}",
+    'Ǖ' => "U\u{0308}\u{0304}",
+    'ǖ' => "u\u{0308}\u{0304}",
+    'Ǘ' => "U\u{0308}\u{0301}",
+    'ǘ' => "u\u{0308}\u{0301}",
+    'Ǚ' => "U\u{0308}\u{030C}",
+    'ǚ' => "u\u{0308}\u{030C}",
+    'Ǜ' => "U\u{0308}\u{0300}",
+    'ǜ' => "u\u{0308}\u{0300}",
+    'Ǟ' => "A\u{0308}\u{0304}",
+    'ǟ' => "a\u{0308}\u{0304}",
+    'Ǡ' => "A\u{0307}\u{0304}",
+    'ǡ' => "a\u{0307}\u{0304}",
+    'Ǧ' => "G\u{030C}",
+    'ǧ' => "g\u{030C}",
+    'Ǩ' => "K\u{030C}",
+    'ǩ' => "k\u{030C}",
+    'Ǫ' => "O\u{0328}",
+    'ǫ' => "o\u{0328}",
+    'Ǭ' => "O\u{0328}\u{0304}",
+    'ǭ' => "o\u{0328}\u{0304}",
+    'ǰ' => "j\u{030C}",
+    'Ǵ' => "G\u{0301}",
+    'ǵ' => "g\u{0301}",
+    'Ǹ' => "N\u{0300}",
+    'ǹ' => "n\u{0300}",
+    'Ǻ' => "A\u{030A}\u{0301}",
+    'ǻ' => "a\u{030A}\u{0301}",
+    'Ȁ' => "A\u{030F}",
+    'ȁ' => "a\u{030F}",
+    'Ȃ' => "A\u{0311}",
+    'ȃ' => "a\u{0311}",
+    'Ȅ' => "E\u{030F}",
+    'ȅ' => "e\u{030F}",
+    'Ȇ' => "E\u{0311}",
+    'ȇ' => "e\u{0311}",
+    'Ȉ' => "I\u{030F}",
+    'ȉ' => "i\u{030F}",
+    'Ȋ' => "I\u{0311}",
+    'ȋ' => "i\u{0311}",
+    'Ȍ' => "O\u{030F}",
+    'ȍ' => "o\u{030F}",
+    'Ȏ' => "O\u{0311}",
+    'ȏ' => "o\u{0311}",
+    'Ȑ' => "R\u{030F}",
+    'ȑ' => "r\u{030F}",
+    'Ȓ' => "R\u{0311}",
+    'ȓ' => "r\u{0311}",
+    'Ȕ' => "U\u{030F}",
+    'ȕ' => "u\u{030F}",
+    'Ȗ' => "U\u{0311}",
+    'ȗ' => "u\u{0311}",
+    'Ș' => "S\u{0326}",
+    'ș' => "s\u{0326}",
+    'Ț' => "T\u{0326}",
+    'ț' => "t\u{0326}",
+    'Ȟ' => "H\u{030C}",
+    'ȟ' => "h\u{030C}",
+    'Ȧ' => "A\u{0307}",
+    'ȧ' => "a\u{0307}",
+    'Ȩ' => "E\u{0327}",
+    'ȩ' => "e\u{0327}",
+    'Ȫ' => "O\u{0308}\u{0304}",
+    'ȫ' => "o\u{0308}\u{0304}",
+    'Ȭ' => "O\u{0303}\u{0304}",
+    'ȭ' => "o\u{0303}\u{0304}",
+    'Ȯ' => "O\u{0307}",
+    'ȯ' => "o\u{0307}",
+    'Ȱ' => "O\u{0307}\u{0304}",
+    'ȱ' => "o\u{0307}\u{0304}",
+    'Ȳ' => "Y\u{0304}",
+    'ȳ' => "y\u{0304}",
+};
+
+/// Explicit rules for letters whose canonical decomposition, if any, does not
+/// end in ASCII letters
+///
+/// Ligatures and special letters expand to ASCII digraphs; stroked and slashed
+/// letters become their base plus an overlay mark (U+0335, U+0337 or U+0338).
+/// Accented forms of these letters keep their accents after the expansion.
+static SUPPLEMENTARY: phf::Map<char, &'static str> = phf_map! {
+    'Æ' => "AE",
+    'æ' => "ae",
+    'Œ' => "OE",
+    'œ' => "oe",
+    'Ĳ' => "IJ",
+    'ĳ' => "ij",
+    'ß' => "ss",
+    'Þ' => "TH",
+    'þ' => "th",
+    'ſ' => "s",
+    'Ø' => "O\u{0338}",
+    'ø' => "o\u{0338}",
+    'Ǣ' => "AE\u{0304}",
+    'ǣ' => "ae\u{0304}",
+    'Ǽ' => "AE\u{0301}",
+    'ǽ' => "ae\u{0301}",
+    'Ǿ' => "O\u{0338}\u{0301}",
+    'ǿ' => "o\u{0338}\u{0301}",
+    'Đ' => "D\u{0335}",
+    'đ' => "d\u{0335}",
+    'Ð' => "D\u{0335}",
+    'ð' => "d\u{0335}",
+    'Ħ' => "H\u{0335}",
+    'ħ' => "h\u{0335}",
+    'Ŧ' => "T\u{0335}",
+    'ŧ' => "t\u{0335}",
+    'Ƀ' => "B\u{0335}",
+    'ƀ' => "b\u{0335}",
+    'Ɨ' => "I\u{0335}",
+    'ɨ' => "i\u{0335}",
+    'Ƶ' => "Z\u{0335}",
+    'ƶ' => "z\u{0335}",
+    'Ł' => "L\u{0337}",
+    'ł' => "l\u{0337}",
+};
+
+/// Decompose an extended Latin letter into ASCII base letters and combining marks
+pub(crate) fn decompose(c: char) -> Option<&'static str> {
+    CANONICAL.get(&c).or_else(|| SUPPLEMENTARY.get(&c)).copied()
+}
+
+/// Whether `c` is a combining diacritical mark (U+0300–U+036F)
+pub(crate) fn is_combining_mark(c: char) -> bool {
+    matches!(c, '\u{0300}'..='\u{036F}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_decomposition() {
+        assert_eq!(decompose('é'), Some("e\u{0301}"));
+        assert_eq!(decompose('Ǖ'), Some("U\u{0308}\u{0304}"));
+        assert_eq!(decompose('Ǻ'), Some("A\u{030A}\u{0301}"));
+    }
+
+    #[test]
+    fn test_supplementary_rules() {
+        assert_eq!(decompose('ß'), Some("ss"));
+        assert_eq!(decompose('ł'), Some("l\u{0337}"));
+        assert_eq!(decompose('Ǿ'), Some("O\u{0338}\u{0301}"));
+        assert_eq!(decompose('x'), None);
+    }
+}
@@ -0,0 +1,256 @@
+use crate::compose::{apply_effects, Effect};
+use crate::grapheme::graphemes;
+
+/// Unit of comparison for [`diff_to_unicode_with`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DiffGranularity {
+    /// Words, whitespace runs and single punctuation marks
+    #[default]
+    Word,
+
+    /// Grapheme clusters, so accents and emoji are never split
+    Char,
+}
+
+/// Render the changes from `old` to `new` as plain text, comparing words
+///
+/// See [`diff_to_unicode_with`].
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::diff_to_unicode;
+///
+/// assert_eq!(
+///     diff_to_unicode("the quick fox", "the slow fox"),
+///     "the q\u{0336}u\u{0336}i\u{0336}c\u{0336}k\u{0336}s\u{0332}l\u{0332}o\u{0332}w\u{0332} fox"
+/// );
+/// ```
+pub fn diff_to_unicode(old: &str, new: &str) -> String {
+    diff_to_unicode_with(old, new, DiffGranularity::Word)
+}
+
+/// Render the changes from `old` to `new` as plain text
+///
+/// Deleted text gets the [`Effect::Strikethrough`] overlay and inserted text
+/// an [`Effect::Underline`]; unchanged text is left plain. Deletions come
+/// before the insertions that replace them. The marks are ordinary combining
+/// characters, so the result survives copy-paste and platforms that strip
+/// ANSI escapes or Markdown. Whitespace and emoji cannot carry marks and are
+/// shown as written.
+///
+/// The diff is a longest common subsequence over tokens, after trimming the
+/// common prefix and suffix; time and memory grow with the product of the
+/// number of changed tokens on each side. Past about four million, the whole
+/// changed middle is shown deleted and then inserted.
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{diff_to_unicode_with, DiffGranularity};
+///
+/// assert_eq!(
+///     diff_to_unicode_with("color", "colour", DiffGranularity::Char),
+///     "colou\u{0332}r"
+/// );
+/// ```
+pub fn diff_to_unicode_with(old: &str, new: &str, granularity: DiffGranularity) -> String {
+    let old = tokenize(old, granularity);
+    let new = tokenize(new, granularity);
+    let mut out = String::new();
+    for (op, text) in diff(&old, &new) {
+        match op {
+            Op::Equal => out.push_str(&text),
+            Op::Delete => out.push_str(&apply_effects(&text, &[Effect::Strikethrough])),
+            Op::Insert => out.push_str(&apply_effects(&text, &[Effect::Underline])),
+        }
+    }
+    out
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenClass {
+    Word,
+    Space,
+    Other,
+}
+
+fn classify(cluster: &str) -> TokenClass {
+    if cluster.chars().all(char::is_whitespace) {
+        TokenClass::Space
+    } else if cluster.starts_with(|c: char| c.is_alphanumeric() || c == '_') {
+        TokenClass::Word
+    } else {
+        TokenClass::Other
+    }
+}
+
+/// Split text into the tokens compared by the diff
+fn tokenize(text: &str, granularity: DiffGranularity) -> Vec<&str> {
+    let clusters = graphemes(text);
+    if granularity == DiffGranularity::Char {
+        return clusters.collect();
+    }
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut class = None;
+    let mut end = 0;
+    for cluster in clusters {
+        let next = classify(cluster);
+        let joins = next != TokenClass::Other && class == Some(next);
+        if !joins && end > start {
+            tokens.push(&text[start..end]);
+            start = end;
+        }
+        class = Some(next);
+        end += cluster.len();
+    }
+    if end > start {
+        tokens.push(&text[start..end]);
+    }
+    tokens
+}
+
+/// Longest-common-subsequence diff, merging adjacent tokens with the same op
+fn diff(old: &[&str], new: &[&str]) -> Vec<(Op, String)> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let a = &old[prefix..old.len() - suffix];
+    let b = &new[prefix..new.len() - suffix];
+
+    let mut ops: Vec<(Op, String)> = Vec::new();
+    let mut push = |op: Op, token: &str| match ops.last_mut() {
+        Some((last, text)) if *last == op => text.push_str(token),
+        _ => ops.push((op, token.to_string())),
+    };
+    old[..prefix].iter().for_each(|t| push(Op::Equal, t));
+    let width = b.len() + 1;
+    match lcs_table(a, b) {
+        Some(lcs) => {
+            let (mut i, mut j) = (0, 0);
+            while i < a.len() || j < b.len() {
+                if i < a.len() && j < b.len() && a[i] == b[j] {
+                    push(Op::Equal, a[i]);
+                    i += 1;
+                    j += 1;
+                } else if j == b.len()
+                    || (i < a.len() && lcs[(i + 1) * width + j] >= lcs[i * width + j + 1])
+                {
+                    push(Op::Delete, a[i]);
+                    i += 1;
+                } else {
+                    push(Op::Insert, b[j]);
+                    j += 1;
+                }
+            }
+        }
+        None => {
+            // Too long to compare: the whole middle is replaced
+            a.iter().for_each(|t| push(Op::Delete, t));
+            b.iter().for_each(|t| push(Op::Insert, t));
+        }
+    }
+    old[old.len() - suffix..]
+        .iter()
+        .for_each(|t| push(Op::Equal, t));
+    ops
+}
+
+/// Most LCS table cells [`diff`] allocates, 16 MiB of `u32`s
+const MAX_LCS_CELLS: usize = 1 << 22;
+
+/// Row-major table where cell `(i, j)` is the length of the LCS of `a[i..]`
+/// and `b[j..]`, or `None` if it would exceed [`MAX_LCS_CELLS`]
+fn lcs_table(a: &[&str], b: &[&str]) -> Option<Vec<u32>> {
+    let width = b.len() + 1;
+    let cells = (a.len() + 1)
+        .checked_mul(width)
+        .filter(|&cells| cells <= MAX_LCS_CELLS)?;
+    let mut lcs = vec![0u32; cells];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i * width + j] = if a[i] == b[j] {
+                lcs[(i + 1) * width + j + 1] + 1
+            } else {
+                lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+            };
+        }
+    }
+    Some(lcs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strike(text: &str) -> String {
+        apply_effects(text, &[Effect::Strikethrough])
+    }
+
+    fn under(text: &str) -> String {
+        apply_effects(text, &[Effect::Underline])
+    }
+
+    #[test]
+    fn test_tokenize_words() {
+        assert_eq!(
+            tokenize("don't  stop, ok?", DiffGranularity::Word),
+            ["don", "'", "t", "  ", "stop", ",", " ", "ok", "?"]
+        );
+    }
+
+    #[test]
+    fn test_identical_and_empty() {
+        assert_eq!(diff_to_unicode("same text", "same text"), "same text");
+        assert_eq!(diff_to_unicode("", "new"), under("new"));
+        assert_eq!(diff_to_unicode("old", ""), strike("old"));
+        assert_eq!(diff_to_unicode("", ""), "");
+    }
+
+    #[test]
+    fn test_word_diff() {
+        let out = diff_to_unicode("fix the parser bug", "fix parser bugs now");
+        let expected = format!(
+            "fix {}parser {}{}",
+            strike("the "),
+            strike("bug"),
+            under("bugs now")
+        );
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn test_char_diff_keeps_clusters() {
+        let out = diff_to_unicode_with("cafe\u{0301}", "cafe", DiffGranularity::Char);
+        assert_eq!(out, "cafe\u{0301}\u{0336}e\u{0332}");
+    }
+
+    #[test]
+    fn test_large_diff_replaces_middle() {
+        let old: Vec<&str> = std::iter::repeat("a").take(3000).chain(["x"]).collect();
+        let new: Vec<&str> = std::iter::once("y")
+            .chain(std::iter::repeat("a").take(3000))
+            .collect();
+        assert_eq!(
+            diff(&old, &new),
+            [
+                (Op::Delete, format!("{}x", "a".repeat(3000))),
+                (Op::Insert, format!("y{}", "a".repeat(3000))),
+            ]
+        );
+        // Small inputs still get a minimal diff
+        assert_eq!(diff(&old[2990..], &new[..11]).len(), 3);
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt;
+
+/// First regional indicator symbol, 🇦 (U+1F1E6)
+const REGIONAL_A: u32 = 0x1F1E6;
+
+/// Error returned by [`flag`] when a country code is not two ASCII letters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidCountryCode(pub String);
+
+impl fmt::Display for InvalidCountryCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid country code: '{}'", self.0)
+    }
+}
+
+impl std::error::Error for InvalidCountryCode {}
+
+/// Build a flag emoji from an ISO 3166-1 alpha-2 country code
+///
+/// The code is case-insensitive. Any two letters are accepted; platforms show
+/// codes they have no flag for as two boxed letters.
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::flag;
+///
+/// assert_eq!(flag("jp").unwrap(), "🇯🇵");
+/// assert!(flag("JPN").is_err());
+/// ```
+///
+/// # Errors
+///
+/// Returns [`InvalidCountryCode`] unless `country_code` is exactly two ASCII
+/// letters.
+pub fn flag(country_code: &str) -> Result<String, InvalidCountryCode> {
+    let letters = country_code.as_bytes();
+    if letters.len() != 2 || !letters.iter().all(u8::is_ascii_alphabetic) {
+        return Err(InvalidCountryCode(country_code.to_string()));
+    }
+    Ok(letters
+        .iter()
+        .filter_map(|b| char::from_u32(REGIONAL_A + u32::from(b.to_ascii_uppercase() - b'A')))
+        .collect())
+}
+
+/// The uppercase country code of a flag emoji, or `None` if `flag` is not
+/// exactly two regional indicator symbols
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{country_code_of, flag, style, Style};
+///
+/// assert_eq!(country_code_of("🇧🇷").as_deref(), Some("BR"));
+/// // Styled letters carry a separator, so they are not a flag
+/// assert_eq!(country_code_of(&style("BR", Style::RegionalIndicator)), None);
+/// ```
+pub fn country_code_of(flag: &str) -> Option<String> {
+    let code: String = flag.chars().map(regional_letter).collect::<Option<_>>()?;
+    (code.len() == 2).then_some(code)
+}
+
+fn regional_letter(c: char) -> Option<char> {
+    let offset = (c as u32).checked_sub(REGIONAL_A)?;
+    (offset < 26).then(|| char::from(b'A' + offset as u8))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{graphemes, style, style_range, Style};
+
+    #[test]
+    fn test_flag_round_trip() {
+        for code in ["US", "GB", "DE", "UA", "ZA"] {
+            assert_eq!(country_code_of(&flag(code).unwrap()).unwrap(), code);
+        }
+        assert_eq!(flag("gB"), flag("GB"));
+    }
+
+    #[test]
+    fn test_invalid_codes() {
+        assert_eq!(
+            flag("U1").unwrap_err().to_string(),
+            "Invalid country code: 'U1'"
+        );
+        assert!(flag("").is_err());
+        assert!(flag("ÜS").is_err());
+        assert_eq!(country_code_of("🇺"), None);
+        assert_eq!(country_code_of("🇺🇸🇬"), None);
+        assert_eq!(country_code_of("US"), None);
+    }
+
+    #[test]
+    fn test_styled_letters_never_fuse() {
+        let styled = style("USA ok", Style::RegionalIndicator);
+        assert_eq!(styled, "🇺\u{200C}🇸\u{200C}🇦 🇴\u{200C}🇰");
+        // One grapheme cluster per letter, plus the space
+        assert_eq!(graphemes(&styled).count(), 6);
+        assert_eq!(
+            style_range("US", 0..8, Style::RegionalIndicator),
+            "🇺\u{200C}🇸"
+        );
+    }
+}
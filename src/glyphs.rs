@@ -13,11 +13,26 @@ impl fmt::Display for UnknownGlyph {
 
 impl std::error::Error for UnknownGlyph {}
 
-/// Perfect hash map of glyph names to Unicode characters (with VS15 applied)
+/// Define the glyph registry from a single list of entries
 ///
-/// All 531 glyphs have VS15 (U+FE0E) variation selector appended for consistent
-/// text rendering across platforms.
-static GLYPHS: phf::Map<&'static str, &'static str> = phf_map! {
+/// Expands to the [`GLYPHS`] perfect hash map used for lookups and the
+/// [`GLYPH_NAMES`] array used for compile-time validation.
+macro_rules! glyph_table {
+    ($($name:tt => $value:tt,)*) => {
+        /// Perfect hash map of glyph names to Unicode characters (with VS15 applied)
+        ///
+        /// All 531 glyphs have VS15 (U+FE0E) variation selector appended for consistent
+        /// text rendering across platforms.
+        static GLYPHS: phf::Map<&'static str, &'static str> = phf_map! {
+            $($name => $value,)*
+        };
+
+        /// Every glyph name, in registry order
+        pub(crate) const GLYPH_NAMES: &[&str] = &[$($name,)*];
+    };
+}
+
+glyph_table! {
     "dot" => "·︎",
     "bullet" => "•︎",
     "dash" => "─︎",
@@ -549,7 +564,7 @@ static GLYPHS: phf::Map<&'static str, &'static str> = phf_map! {
     "chess.bishop.black" => "♝︎",
     "chess.knight.black" => "♞︎",
     "chess.pawn.black" => "♟︎",
-};
+}
 
 /// Look up a glyph by name
 ///
@@ -624,6 +639,12 @@ mod tests {
         assert_eq!(glyph_names().count(), 531);
     }
 
+    #[test]
+    fn test_glyph_names_match_map() {
+        assert_eq!(GLYPH_NAMES.len(), GLYPHS.len());
+        assert!(GLYPH_NAMES.iter().all(|name| GLYPHS.contains_key(name)));
+    }
+
     #[test]
     fn test_vs15_applied() {
         let star = glyph("star.filled").unwrap();
@@ -0,0 +1,819 @@
+/// Grapheme_Cluster_Break property values (UAX #29)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Gcb {
+    Other,
+    Cr,
+    Lf,
+    Control,
+    Extend,
+    Zwj,
+    RegionalIndicator,
+    Prepend,
+    SpacingMark,
+    L,
+    V,
+    T,
+    Lv,
+    Lvt,
+    ExtendedPictographic,
+}
+
+use Gcb::*;
+
+/// Grapheme_Cluster_Break ranges, sorted by start (Unicode 14.0)
+///
+/// Extended_Pictographic is folded in since no character has both properties.
+/// Precomposed Hangul syllables (LV/LVT) are computed in [`gcb`] instead.
+static GRAPHEME_BREAK: &[(char, char, Gcb)] = &[
+    ('\u{0000}', '\u{0009}', Control),
+    ('\u{000A}', '\u{000A}', Lf),
+    ('\u{000B}', '\u{000C}', Control),
+    ('\u{000D}', '\u{000D}', Cr),
+    ('\u{000E}', '\u{001F}', Control),
+    ('\u{007F}', '\u{009F}', Control),
+    ('\u{00A9}', '\u{00A9}', ExtendedPictographic),
+    ('\u{00AD}', '\u{00AD}', Control),
+    ('\u{00AE}', '\u{00AE}', ExtendedPictographic),
+    ('\u{0300}', '\u{036F}', Extend),
+    ('\u{0483}', '\u{0489}', Extend),
+    ('\u{0591}', '\u{05BD}', Extend),
+    ('\u{05BF}', '\u{05BF}', Extend),
+    ('\u{05C1}', '\u{05C2}', Extend),
+    ('\u{05C4}', '\u{05C5}', Extend),
+    ('\u{05C7}', '\u{05C7}', Extend),
+    ('\u{0600}', '\u{0605}', Prepend),
+    ('\u{0610}', '\u{061A}', Extend),
+    ('\u{061C}', '\u{061C}', Control),
+    ('\u{064B}', '\u{065F}', Extend),
+    ('\u{0670}', '\u{0670}', Extend),
+    ('\u{06D6}', '\u{06DC}', Extend),
+    ('\u{06DD}', '\u{06DD}', Prepend),
+    ('\u{06DF}', '\u{06E4}', Extend),
+    ('\u{06E7}', '\u{06E8}', Extend),
+    ('\u{06EA}', '\u{06ED}', Extend),
+    ('\u{070F}', '\u{070F}', Prepend),
+    ('\u{0711}', '\u{0711}', Extend),
+    ('\u{0730}', '\u{074A}', Extend),
+    ('\u{07A6}', '\u{07B0}', Extend),
+    ('\u{07EB}', '\u{07F3}', Extend),
+    ('\u{07FD}', '\u{07FD}', Extend),
+    ('\u{0816}', '\u{0819}', Extend),
+    ('\u{081B}', '\u{0823}', Extend),
+    ('\u{0825}', '\u{0827}', Extend),
+    ('\u{0829}', '\u{082D}', Extend),
+    ('\u{0859}', '\u{085B}', Extend),
+    ('\u{0890}', '\u{0891}', Prepend),
+    ('\u{0898}', '\u{089F}', Extend),
+    ('\u{08CA}', '\u{08E1}', Extend),
+    ('\u{08E2}', '\u{08E2}', Prepend),
+    ('\u{08E3}', '\u{0902}', Extend),
+    ('\u{0903}', '\u{0903}', SpacingMark),
+    ('\u{093A}', '\u{093A}', Extend),
+    ('\u{093B}', '\u{093B}', SpacingMark),
+    ('\u{093C}', '\u{093C}', Extend),
+    ('\u{093E}', '\u{0940}', SpacingMark),
+    ('\u{0941}', '\u{0948}', Extend),
+    ('\u{0949}', '\u{094C}', SpacingMark),
+    ('\u{094D}', '\u{094D}', Extend),
+    ('\u{094E}', '\u{094F}', SpacingMark),
+    ('\u{0951}', '\u{0957}', Extend),
+    ('\u{0962}', '\u{0963}', Extend),
+    ('\u{0981}', '\u{0981}', Extend),
+    ('\u{0982}', '\u{0983}', SpacingMark),
+    ('\u{09BC}', '\u{09BC}', Extend),
+    ('\u{09BE}', '\u{09BE}', Extend),
+    ('\u{09BF}', '\u{09C0}', SpacingMark),
+    ('\u{09C1}', '\u{09C4}', Extend),
+    ('\u{09C7}', '\u{09C8}', SpacingMark),
+    ('\u{09CB}', '\u{09CC}', SpacingMark),
+    ('\u{09CD}', '\u{09CD}', Extend),
+    ('\u{09D7}', '\u{09D7}', Extend),
+    ('\u{09E2}', '\u{09E3}', Extend),
+    ('\u{09FE}', '\u{09FE}', Extend),
+    ('\u{0A01}', '\u{0A02}', Extend),
+    ('\u{0A03}', '\u{0A03}', SpacingMark),
+    ('\u{0A3C}', '\u{0A3C}', Extend),
+    ('\u{0A3E}', '\u{0A40}', SpacingMark),
+    ('\u{0A41}', '\u{0A42}', Extend),
+    ('\u{0A47}', '\u{0A48}', Extend),
+    ('\u{0A4B}', '\u{0A4D}', Extend),
+    ('\u{0A51}', '\u{0A51}', Extend),
+    ('\u{0A70}', '\u{0A71}', Extend),
+    ('\u{0A75}', '\u{0A75}', Extend),
+    ('\u{0A81}', '\u{0A82}', Extend),
+    ('\u{0A83}', '\u{0A83}', SpacingMark),
+    ('\u{0ABC}', '\u{0ABC}', Extend),
+    ('\u{0ABE}', '\u{0AC0}', SpacingMark),
+    ('\u{0AC1}', '\u{0AC5}', Extend),
+    ('\u{0AC7}', '\u{0AC8}', Extend),
+    ('\u{0AC9}', '\u{0AC9}', SpacingMark),
+    ('\u{0ACB}', '\u{0ACC}', SpacingMark),
+    ('\u{0ACD}', '\u{0ACD}', Extend),
+    ('\u{0AE2}', '\u{0AE3}', Extend),
+    ('\u{0AFA}', '\u{0AFF}', Extend),
+    ('\u{0B01}', '\u{0B01}', Extend),
+    ('\u{0B02}', '\u{0B03}', SpacingMark),
+    ('\u{0B3C}', '\u{0B3C}', Extend),
+    ('\u{0B3E}', '\u{0B3F}', Extend),
+    ('\u{0B40}', '\u{0B40}', SpacingMark),
+    ('\u{0B41}', '\u{0B44}', Extend),
+    ('\u{0B47}', '\u{0B48}', SpacingMark),
+    ('\u{0B4B}', '\u{0B4C}', SpacingMark),
+    ('\u{0B4D}', '\u{0B4D}', Extend),
+    ('\u{0B55}', '\u{0B57}', Extend),
+    ('\u{0B62}', '\u{0B63}', Extend),
+    ('\u{0B82}', '\u{0B82}', Extend),
+    ('\u{0BBE}', '\u{0BBE}', Extend),
+    ('\u{0BBF}', '\u{0BBF}', SpacingMark),
+    ('\u{0BC0}', '\u{0BC0}', Extend),
+    ('\u{0BC1}', '\u{0BC2}', SpacingMark),
+    ('\u{0BC6}', '\u{0BC8}', SpacingMark),
+    ('\u{0BCA}', '\u{0BCC}', SpacingMark),
+    ('\u{0BCD}', '\u{0BCD}', Extend),
+    ('\u{0BD7}', '\u{0BD7}', Extend),
+    ('\u{0C00}', '\u{0C00}', Extend),
+    ('\u{0C01}', '\u{0C03}', SpacingMark),
+    ('\u{0C04}', '\u{0C04}', Extend),
+    ('\u{0C3C}', '\u{0C3C}', Extend),
+    ('\u{0C3E}', '\u{0C40}', Extend),
+    ('\u{0C41}', '\u{0C44}', SpacingMark),
+    ('\u{0C46}', '\u{0C48}', Extend),
+    ('\u{0C4A}', '\u{0C4D}', Extend),
+    ('\u{0C55}', '\u{0C56}', Extend),
+    ('\u{0C62}', '\u{0C63}', Extend),
+    ('\u{0C81}', '\u{0C81}', Extend),
+    ('\u{0C82}', '\u{0C83}', SpacingMark),
+    ('\u{0CBC}', '\u{0CBC}', Extend),
+    ('\u{0CBE}', '\u{0CBE}', SpacingMark),
+    ('\u{0CBF}', '\u{0CBF}', Extend),
+    ('\u{0CC0}', '\u{0CC1}', SpacingMark),
+    ('\u{0CC2}', '\u{0CC2}', Extend),
+    ('\u{0CC3}', '\u{0CC4}', SpacingMark),
+    ('\u{0CC6}', '\u{0CC6}', Extend),
+    ('\u{0CC7}', '\u{0CC8}', SpacingMark),
+    ('\u{0CCA}', '\u{0CCB}', SpacingMark),
+    ('\u{0CCC}', '\u{0CCD}', Extend),
+    ('\u{0CD5}', '\u{0CD6}', Extend),
+    ('\u{0CE2}', '\u{0CE3}', Extend),
+    ('\u{0D00}', '\u{0D01}', Extend),
+    ('\u{0D02}', '\u{0D03}', SpacingMark),
+    ('\u{0D3B}', '\u{0D3C}', Extend),
+    ('\u{0D3E}', '\u{0D3E}', Extend),
+    ('\u{0D3F}', '\u{0D40}', SpacingMark),
+    ('\u{0D41}', '\u{0D44}', Extend),
+    ('\u{0D46}', '\u{0D48}', SpacingMark),
+    ('\u{0D4A}', '\u{0D4C}', SpacingMark),
+    ('\u{0D4D}', '\u{0D4D}', Extend),
+    ('\u{0D4E}', '\u{0D4E}', Prepend),
+    ('\u{0D57}', '\u{0D57}', Extend),
+    ('\u{0D62}', '\u{0D63}', Extend),
+    ('\u{0D81}', '\u{0D81}', Extend),
+    ('\u{0D82}', '\u{0D83}', SpacingMark),
+    ('\u{0DCA}', '\u{0DCA}', Extend),
+    ('\u{0DCF}', '\u{0DCF}', Extend),
+    ('\u{0DD0}', '\u{0DD1}', SpacingMark),
+    ('\u{0DD2}', '\u{0DD4}', Extend),
+    ('\u{0DD6}', '\u{0DD6}', Extend),
+    ('\u{0DD8}', '\u{0DDE}', SpacingMark),
+    ('\u{0DDF}', '\u{0DDF}', Extend),
+    ('\u{0DF2}', '\u{0DF3}', SpacingMark),
+    ('\u{0E31}', '\u{0E31}', Extend),
+    ('\u{0E33}', '\u{0E33}', SpacingMark),
+    ('\u{0E34}', '\u{0E3A}', Extend),
+    ('\u{0E47}', '\u{0E4E}', Extend),
+    ('\u{0EB1}', '\u{0EB1}', Extend),
+    ('\u{0EB3}', '\u{0EB3}', SpacingMark),
+    ('\u{0EB4}', '\u{0EBC}', Extend),
+    ('\u{0EC8}', '\u{0ECD}', Extend),
+    ('\u{0F18}', '\u{0F19}', Extend),
+    ('\u{0F35}', '\u{0F35}', Extend),
+    ('\u{0F37}', '\u{0F37}', Extend),
+    ('\u{0F39}', '\u{0F39}', Extend),
+    ('\u{0F3E}', '\u{0F3F}', SpacingMark),
+    ('\u{0F71}', '\u{0F7E}', Extend),
+    ('\u{0F7F}', '\u{0F7F}', SpacingMark),
+    ('\u{0F80}', '\u{0F84}', Extend),
+    ('\u{0F86}', '\u{0F87}', Extend),
+    ('\u{0F8D}', '\u{0F97}', Extend),
+    ('\u{0F99}', '\u{0FBC}', Extend),
+    ('\u{0FC6}', '\u{0FC6}', Extend),
+    ('\u{102D}', '\u{1030}', Extend),
+    ('\u{1031}', '\u{1031}', SpacingMark),
+    ('\u{1032}', '\u{1037}', Extend),
+    ('\u{1039}', '\u{103A}', Extend),
+    ('\u{103B}', '\u{103C}', SpacingMark),
+    ('\u{103D}', '\u{103E}', Extend),
+    ('\u{1056}', '\u{1057}', SpacingMark),
+    ('\u{1058}', '\u{1059}', Extend),
+    ('\u{105E}', '\u{1060}', Extend),
+    ('\u{1071}', '\u{1074}', Extend),
+    ('\u{1082}', '\u{1082}', Extend),
+    ('\u{1084}', '\u{1084}', SpacingMark),
+    ('\u{1085}', '\u{1086}', Extend),
+    ('\u{108D}', '\u{108D}', Extend),
+    ('\u{109D}', '\u{109D}', Extend),
+    ('\u{1100}', '\u{115F}', L),
+    ('\u{1160}', '\u{11A7}', V),
+    ('\u{11A8}', '\u{11FF}', T),
+    ('\u{135D}', '\u{135F}', Extend),
+    ('\u{1712}', '\u{1714}', Extend),
+    ('\u{1715}', '\u{1715}', SpacingMark),
+    ('\u{1732}', '\u{1733}', Extend),
+    ('\u{1734}', '\u{1734}', SpacingMark),
+    ('\u{1752}', '\u{1753}', Extend),
+    ('\u{1772}', '\u{1773}', Extend),
+    ('\u{17B4}', '\u{17B5}', Extend),
+    ('\u{17B6}', '\u{17B6}', SpacingMark),
+    ('\u{17B7}', '\u{17BD}', Extend),
+    ('\u{17BE}', '\u{17C5}', SpacingMark),
+    ('\u{17C6}', '\u{17C6}', Extend),
+    ('\u{17C7}', '\u{17C8}', SpacingMark),
+    ('\u{17C9}', '\u{17D3}', Extend),
+    ('\u{17DD}', '\u{17DD}', Extend),
+    ('\u{180B}', '\u{180D}', Extend),
+    ('\u{180E}', '\u{180E}', Control),
+    ('\u{180F}', '\u{180F}', Extend),
+    ('\u{1885}', '\u{1886}', Extend),
+    ('\u{18A9}', '\u{18A9}', Extend),
+    ('\u{1920}', '\u{1922}', Extend),
+    ('\u{1923}', '\u{1926}', SpacingMark),
+    ('\u{1927}', '\u{1928}', Extend),
+    ('\u{1929}', '\u{192B}', SpacingMark),
+    ('\u{1930}', '\u{1931}', SpacingMark),
+    ('\u{1932}', '\u{1932}', Extend),
+    ('\u{1933}', '\u{1938}', SpacingMark),
+    ('\u{1939}', '\u{193B}', Extend),
+    ('\u{1A17}', '\u{1A18}', Extend),
+    ('\u{1A19}', '\u{1A1A}', SpacingMark),
+    ('\u{1A1B}', '\u{1A1B}', Extend),
+    ('\u{1A55}', '\u{1A55}', SpacingMark),
+    ('\u{1A56}', '\u{1A56}', Extend),
+    ('\u{1A57}', '\u{1A57}', SpacingMark),
+    ('\u{1A58}', '\u{1A5E}', Extend),
+    ('\u{1A60}', '\u{1A60}', Extend),
+    ('\u{1A62}', '\u{1A62}', Extend),
+    ('\u{1A65}', '\u{1A6C}', Extend),
+    ('\u{1A6D}', '\u{1A72}', SpacingMark),
+    ('\u{1A73}', '\u{1A7C}', Extend),
+    ('\u{1A7F}', '\u{1A7F}', Extend),
+    ('\u{1AB0}', '\u{1ACE}', Extend),
+    ('\u{1B00}', '\u{1B03}', Extend),
+    ('\u{1B04}', '\u{1B04}', SpacingMark),
+    ('\u{1B34}', '\u{1B3A}', Extend),
+    ('\u{1B3B}', '\u{1B3B}', SpacingMark),
+    ('\u{1B3C}', '\u{1B3C}', Extend),
+    ('\u{1B3D}', '\u{1B41}', SpacingMark),
+    ('\u{1B42}', '\u{1B42}', Extend),
+    ('\u{1B43}', '\u{1B44}', SpacingMark),
+    ('\u{1B6B}', '\u{1B73}', Extend),
+    ('\u{1B80}', '\u{1B81}', Extend),
+    ('\u{1B82}', '\u{1B82}', SpacingMark),
+    ('\u{1BA1}', '\u{1BA1}', SpacingMark),
+    ('\u{1BA2}', '\u{1BA5}', Extend),
+    ('\u{1BA6}', '\u{1BA7}', SpacingMark),
+    ('\u{1BA8}', '\u{1BA9}', Extend),
+    ('\u{1BAA}', '\u{1BAA}', SpacingMark),
+    ('\u{1BAB}', '\u{1BAD}', Extend),
+    ('\u{1BE6}', '\u{1BE6}', Extend),
+    ('\u{1BE7}', '\u{1BE7}', SpacingMark),
+    ('\u{1BE8}', '\u{1BE9}', Extend),
+    ('\u{1BEA}', '\u{1BEC}', SpacingMark),
+    ('\u{1BED}', '\u{1BED}', Extend),
+    ('\u{1BEE}', '\u{1BEE}', SpacingMark),
+    ('\u{1BEF}', '\u{1BF1}', Extend),
+    ('\u{1BF2}', '\u{1BF3}', SpacingMark),
+    ('\u{1C24}', '\u{1C2B}', SpacingMark),
+    ('\u{1C2C}', '\u{1C33}', Extend),
+    ('\u{1C34}', '\u{1C35}', SpacingMark),
+    ('\u{1C36}', '\u{1C37}', Extend),
+    ('\u{1CD0}', '\u{1CD2}', Extend),
+    ('\u{1CD4}', '\u{1CE0}', Extend),
+    ('\u{1CE1}', '\u{1CE1}', SpacingMark),
+    ('\u{1CE2}', '\u{1CE8}', Extend),
+    ('\u{1CED}', '\u{1CED}', Extend),
+    ('\u{1CF4}', '\u{1CF4}', Extend),
+    ('\u{1CF7}', '\u{1CF7}', SpacingMark),
+    ('\u{1CF8}', '\u{1CF9}', Extend),
+    ('\u{1DC0}', '\u{1DFF}', Extend),
+    ('\u{200B}', '\u{200B}', Control),
+    ('\u{200C}', '\u{200C}', Extend),
+    ('\u{200D}', '\u{200D}', Zwj),
+    ('\u{200E}', '\u{200F}', Control),
+    ('\u{2028}', '\u{202E}', Control),
+    ('\u{203C}', '\u{203C}', ExtendedPictographic),
+    ('\u{2049}', '\u{2049}', ExtendedPictographic),
+    ('\u{2060}', '\u{2064}', Control),
+    ('\u{2066}', '\u{206F}', Control),
+    ('\u{20D0}', '\u{20F0}', Extend),
+    ('\u{2122}', '\u{2122}', ExtendedPictographic),
+    ('\u{2139}', '\u{2139}', ExtendedPictographic),
+    ('\u{2194}', '\u{2199}', ExtendedPictographic),
+    ('\u{21A9}', '\u{21AA}', ExtendedPictographic),
+    ('\u{231A}', '\u{231B}', ExtendedPictographic),
+    ('\u{2328}', '\u{2328}', ExtendedPictographic),
+    ('\u{2388}', '\u{2388}', ExtendedPictographic),
+    ('\u{23CF}', '\u{23CF}', ExtendedPictographic),
+    ('\u{23E9}', '\u{23F3}', ExtendedPictographic),
+    ('\u{23F8}', '\u{23FA}', ExtendedPictographic),
+    ('\u{24C2}', '\u{24C2}', ExtendedPictographic),
+    ('\u{25AA}', '\u{25AB}', ExtendedPictographic),
+    ('\u{25B6}', '\u{25B6}', ExtendedPictographic),
+    ('\u{25C0}', '\u{25C0}', ExtendedPictographic),
+    ('\u{25FB}', '\u{25FE}', ExtendedPictographic),
+    ('\u{2600}', '\u{2605}', ExtendedPictographic),
+    ('\u{2607}', '\u{2612}', ExtendedPictographic),
+    ('\u{2614}', '\u{2685}', ExtendedPictographic),
+    ('\u{2690}', '\u{2705}', ExtendedPictographic),
+    ('\u{2708}', '\u{2712}', ExtendedPictographic),
+    ('\u{2714}', '\u{2714}', ExtendedPictographic),
+    ('\u{2716}', '\u{2716}', ExtendedPictographic),
+    ('\u{271D}', '\u{271D}', ExtendedPictographic),
+    ('\u{2721}', '\u{2721}', ExtendedPictographic),
+    ('\u{2728}', '\u{2728}', ExtendedPictographic),
+    ('\u{2733}', '\u{2734}', ExtendedPictographic),
+    ('\u{2744}', '\u{2744}', ExtendedPictographic),
+    ('\u{2747}', '\u{2747}', ExtendedPictographic),
+    ('\u{274C}', '\u{274C}', ExtendedPictographic),
+    ('\u{274E}', '\u{274E}', ExtendedPictographic),
+    ('\u{2753}', '\u{2755}', ExtendedPictographic),
+    ('\u{2757}', '\u{2757}', ExtendedPictographic),
+    ('\u{2763}', '\u{2767}', ExtendedPictographic),
+    ('\u{2795}', '\u{2797}', ExtendedPictographic),
+    ('\u{27A1}', '\u{27A1}', ExtendedPictographic),
+    ('\u{27B0}', '\u{27B0}', ExtendedPictographic),
+    ('\u{27BF}', '\u{27BF}', ExtendedPictographic),
+    ('\u{2934}', '\u{2935}', ExtendedPictographic),
+    ('\u{2B05}', '\u{2B07}', ExtendedPictographic),
+    ('\u{2B1B}', '\u{2B1C}', ExtendedPictographic),
+    ('\u{2B50}', '\u{2B50}', ExtendedPictographic),
+    ('\u{2B55}', '\u{2B55}', ExtendedPictographic),
+    ('\u{2CEF}', '\u{2CF1}', Extend),
+    ('\u{2D7F}', '\u{2D7F}', Extend),
+    ('\u{2DE0}', '\u{2DFF}', Extend),
+    ('\u{302A}', '\u{302F}', Extend),
+    ('\u{3030}', '\u{3030}', ExtendedPictographic),
+    ('\u{303D}', '\u{303D}', ExtendedPictographic),
+    ('\u{3099}', '\u{309A}', Extend),
+    ('\u{3297}', '\u{3297}', ExtendedPictographic),
+    ('\u{3299}', '\u{3299}', ExtendedPictographic),
+    ('\u{A66F}', '\u{A672}', Extend),
+    ('\u{A674}', '\u{A67D}', Extend),
+    ('\u{A69E}', '\u{A69F}', Extend),
+    ('\u{A6F0}', '\u{A6F1}', Extend),
+    ('\u{A802}', '\u{A802}', Extend),
+    ('\u{A806}', '\u{A806}', Extend),
+    ('\u{A80B}', '\u{A80B}', Extend),
+    ('\u{A823}', '\u{A824}', SpacingMark),
+    ('\u{A825}', '\u{A826}', Extend),
+    ('\u{A827}', '\u{A827}', SpacingMark),
+    ('\u{A82C}', '\u{A82C}', Extend),
+    ('\u{A880}', '\u{A881}', SpacingMark),
+    ('\u{A8B4}', '\u{A8C3}', SpacingMark),
+    ('\u{A8C4}', '\u{A8C5}', Extend),
+    ('\u{A8E0}', '\u{A8F1}', Extend),
+    ('\u{A8FF}', '\u{A8FF}', Extend),
+    ('\u{A926}', '\u{A92D}', Extend),
+    ('\u{A947}', '\u{A951}', Extend),
+    ('\u{A952}', '\u{A953}', SpacingMark),
+    ('\u{A960}', '\u{A97C}', L),
+    ('\u{A980}', '\u{A982}', Extend),
+    ('\u{A983}', '\u{A983}', SpacingMark),
+    ('\u{A9B3}', '\u{A9B3}', Extend),
+    ('\u{A9B4}', '\u{A9B5}', SpacingMark),
+    ('\u{A9B6}', '\u{A9B9}', Extend),
+    ('\u{A9BA}', '\u{A9BB}', SpacingMark),
+    ('\u{A9BC}', '\u{A9BD}', Extend),
+    ('\u{A9BE}', '\u{A9C0}', SpacingMark),
+    ('\u{A9E5}', '\u{A9E5}', Extend),
+    ('\u{AA29}', '\u{AA2E}', Extend),
+    ('\u{AA2F}', '\u{AA30}', SpacingMark),
+    ('\u{AA31}', '\u{AA32}', Extend),
+    ('\u{AA33}', '\u{AA34}', SpacingMark),
+    ('\u{AA35}', '\u{AA36}', Extend),
+    ('\u{AA43}', '\u{AA43}', Extend),
+    ('\u{AA4C}', '\u{AA4C}', Extend),
+    ('\u{AA4D}', '\u{AA4D}', SpacingMark),
+    ('\u{AA7C}', '\u{AA7C}', Extend),
+    ('\u{AAB0}', '\u{AAB0}', Extend),
+    ('\u{AAB2}', '\u{AAB4}', Extend),
+    ('\u{AAB7}', '\u{AAB8}', Extend),
+    ('\u{AABE}', '\u{AABF}', Extend),
+    ('\u{AAC1}', '\u{AAC1}', Extend),
+    ('\u{AAEB}', '\u{AAEB}', SpacingMark),
+    ('\u{AAEC}', '\u{AAED}', Extend),
+    ('\u{AAEE}', '\u{AAEF}', SpacingMark),
+    ('\u{AAF5}', '\u{AAF5}', SpacingMark),
+    ('\u{AAF6}', '\u{AAF6}', Extend),
+    ('\u{ABE3}', '\u{ABE4}', SpacingMark),
+    ('\u{ABE5}', '\u{ABE5}', Extend),
+    ('\u{ABE6}', '\u{ABE7}', SpacingMark),
+    ('\u{ABE8}', '\u{ABE8}', Extend),
+    ('\u{ABE9}', '\u{ABEA}', SpacingMark),
+    ('\u{ABEC}', '\u{ABEC}', SpacingMark),
+    ('\u{ABED}', '\u{ABED}', Extend),
+    ('\u{D7B0}', '\u{D7C6}', V),
+    ('\u{D7CB}', '\u{D7FB}', T),
+    ('\u{FB1E}', '\u{FB1E}', Extend),
+    ('\u{FE00}', '\u{FE0F}', Extend),
+    ('\u{FE20}', '\u{FE2F}', Extend),
+    ('\u{FEFF}', '\u{FEFF}', Control),
+    ('\u{FF9E}', '\u{FF9F}', Extend),
+    ('\u{FFF9}', '\u{FFFB}', Control),
+    ('\u{101FD}', '\u{101FD}', Extend),
+    ('\u{102E0}', '\u{102E0}', Extend),
+    ('\u{10376}', '\u{1037A}', Extend),
+    ('\u{10A01}', '\u{10A03}', Extend),
+    ('\u{10A05}', '\u{10A06}', Extend),
+    ('\u{10A0C}', '\u{10A0F}', Extend),
+    ('\u{10A38}', '\u{10A3A}', Extend),
+    ('\u{10A3F}', '\u{10A3F}', Extend),
+    ('\u{10AE5}', '\u{10AE6}', Extend),
+    ('\u{10D24}', '\u{10D27}', Extend),
+    ('\u{10EAB}', '\u{10EAC}', Extend),
+    ('\u{10F46}', '\u{10F50}', Extend),
+    ('\u{10F82}', '\u{10F85}', Extend),
+    ('\u{11000}', '\u{11000}', SpacingMark),
+    ('\u{11001}', '\u{11001}', Extend),
+    ('\u{11002}', '\u{11002}', SpacingMark),
+    ('\u{11038}', '\u{11046}', Extend),
+    ('\u{11070}', '\u{11070}', Extend),
+    ('\u{11073}', '\u{11074}', Extend),
+    ('\u{1107F}', '\u{11081}', Extend),
+    ('\u{11082}', '\u{11082}', SpacingMark),
+    ('\u{110B0}', '\u{110B2}', SpacingMark),
+    ('\u{110B3}', '\u{110B6}', Extend),
+    ('\u{110B7}', '\u{110B8}', SpacingMark),
+    ('\u{110B9}', '\u{110BA}', Extend),
+    ('\u{110BD}', '\u{110BD}', Prepend),
+    ('\u{110C2}', '\u{110C2}', Extend),
+    ('\u{110CD}', '\u{110CD}', Prepend),
+    ('\u{11100}', '\u{11102}', Extend),
+    ('\u{11127}', '\u{1112B}', Extend),
+    ('\u{1112C}', '\u{1112C}', SpacingMark),
+    ('\u{1112D}', '\u{11134}', Extend),
+    ('\u{11145}', '\u{11146}', SpacingMark),
+    ('\u{11173}', '\u{11173}', Extend),
+    ('\u{11180}', '\u{11181}', Extend),
+    ('\u{11182}', '\u{11182}', SpacingMark),
+    ('\u{111B3}', '\u{111B5}', SpacingMark),
+    ('\u{111B6}', '\u{111BE}', Extend),
+    ('\u{111BF}', '\u{111C0}', SpacingMark),
+    ('\u{111C2}', '\u{111C3}', Prepend),
+    ('\u{111C9}', '\u{111CC}', Extend),
+    ('\u{111CE}', '\u{111CE}', SpacingMark),
+    ('\u{111CF}', '\u{111CF}', Extend),
+    ('\u{1122C}', '\u{1122E}', SpacingMark),
+    ('\u{1122F}', '\u{11231}', Extend),
+    ('\u{11232}', '\u{11233}', SpacingMark),
+    ('\u{11234}', '\u{11234}', Extend),
+    ('\u{11235}', '\u{11235}', SpacingMark),
+    ('\u{11236}', '\u{11237}', Extend),
+    ('\u{1123E}', '\u{1123E}', Extend),
+    ('\u{112DF}', '\u{112DF}', Extend),
+    ('\u{112E0}', '\u{112E2}', SpacingMark),
+    ('\u{112E3}', '\u{112EA}', Extend),
+    ('\u{11300}', '\u{11301}', Extend),
+    ('\u{11302}', '\u{11303}', SpacingMark),
+    ('\u{1133B}', '\u{1133C}', Extend),
+    ('\u{1133E}', '\u{1133E}', Extend),
+    ('\u{1133F}', '\u{1133F}', SpacingMark),
+    ('\u{11340}', '\u{11340}', Extend),
+    ('\u{11341}', '\u{11344}', SpacingMark),
+    ('\u{11347}', '\u{11348}', SpacingMark),
+    ('\u{1134B}', '\u{1134D}', SpacingMark),
+    ('\u{11357}', '\u{11357}', Extend),
+    ('\u{11362}', '\u{11363}', SpacingMark),
+    ('\u{11366}', '\u{1136C}', Extend),
+    ('\u{11370}', '\u{11374}', Extend),
+    ('\u{11435}', '\u{11437}', SpacingMark),
+    ('\u{11438}', '\u{1143F}', Extend),
+    ('\u{11440}', '\u{11441}', SpacingMark),
+    ('\u{11442}', '\u{11444}', Extend),
+    ('\u{11445}', '\u{11445}', SpacingMark),
+    ('\u{11446}', '\u{11446}', Extend),
+    ('\u{1145E}', '\u{1145E}', Extend),
+    ('\u{114B0}', '\u{114B0}', Extend),
+    ('\u{114B1}', '\u{114B2}', SpacingMark),
+    ('\u{114B3}', '\u{114B8}', Extend),
+    ('\u{114B9}', '\u{114B9}', SpacingMark),
+    ('\u{114BA}', '\u{114BA}', Extend),
+    ('\u{114BB}', '\u{114BC}', SpacingMark),
+    ('\u{114BD}', '\u{114BD}', Extend),
+    ('\u{114BE}', '\u{114BE}', SpacingMark),
+    ('\u{114BF}', '\u{114C0}', Extend),
+    ('\u{114C1}', '\u{114C1}', SpacingMark),
+    ('\u{114C2}', '\u{114C3}', Extend),
+    ('\u{115AF}', '\u{115AF}', Extend),
+    ('\u{115B0}', '\u{115B1}', SpacingMark),
+    ('\u{115B2}', '\u{115B5}', Extend),
+    ('\u{115B8}', '\u{115BB}', SpacingMark),
+    ('\u{115BC}', '\u{115BD}', Extend),
+    ('\u{115BE}', '\u{115BE}', SpacingMark),
+    ('\u{115BF}', '\u{115C0}', Extend),
+    ('\u{115DC}', '\u{115DD}', Extend),
+    ('\u{11630}', '\u{11632}', SpacingMark),
+    ('\u{11633}', '\u{1163A}', Extend),
+    ('\u{1163B}', '\u{1163C}', SpacingMark),
+    ('\u{1163D}', '\u{1163D}', Extend),
+    ('\u{1163E}', '\u{1163E}', SpacingMark),
+    ('\u{1163F}', '\u{11640}', Extend),
+    ('\u{116AB}', '\u{116AB}', Extend),
+    ('\u{116AC}', '\u{116AC}', SpacingMark),
+    ('\u{116AD}', '\u{116AD}', Extend),
+    ('\u{116AE}', '\u{116AF}', SpacingMark),
+    ('\u{116B0}', '\u{116B5}', Extend),
+    ('\u{116B6}', '\u{116B6}', SpacingMark),
+    ('\u{116B7}', '\u{116B7}', Extend),
+    ('\u{1171D}', '\u{1171F}', Extend),
+    ('\u{11722}', '\u{11725}', Extend),
+    ('\u{11726}', '\u{11726}', SpacingMark),
+    ('\u{11727}', '\u{1172B}', Extend),
+    ('\u{1182C}', '\u{1182E}', SpacingMark),
+    ('\u{1182F}', '\u{11837}', Extend),
+    ('\u{11838}', '\u{11838}', SpacingMark),
+    ('\u{11839}', '\u{1183A}', Extend),
+    ('\u{11930}', '\u{11930}', Extend),
+    ('\u{11931}', '\u{11935}', SpacingMark),
+    ('\u{11937}', '\u{11938}', SpacingMark),
+    ('\u{1193B}', '\u{1193C}', Extend),
+    ('\u{1193D}', '\u{1193D}', SpacingMark),
+    ('\u{1193E}', '\u{1193E}', Extend),
+    ('\u{1193F}', '\u{1193F}', Prepend),
+    ('\u{11940}', '\u{11940}', SpacingMark),
+    ('\u{11941}', '\u{11941}', Prepend),
+    ('\u{11942}', '\u{11942}', SpacingMark),
+    ('\u{11943}', '\u{11943}', Extend),
+    ('\u{119D1}', '\u{119D3}', SpacingMark),
+    ('\u{119D4}', '\u{119D7}', Extend),
+    ('\u{119DA}', '\u{119DB}', Extend),
+    ('\u{119DC}', '\u{119DF}', SpacingMark),
+    ('\u{119E0}', '\u{119E0}', Extend),
+    ('\u{119E4}', '\u{119E4}', SpacingMark),
+    ('\u{11A01}', '\u{11A0A}', Extend),
+    ('\u{11A33}', '\u{11A38}', Extend),
+    ('\u{11A39}', '\u{11A39}', SpacingMark),
+    ('\u{11A3A}', '\u{11A3A}', Prepend),
+    ('\u{11A3B}', '\u{11A3E}', Extend),
+    ('\u{11A47}', '\u{11A47}', Extend),
+    ('\u{11A51}', '\u{11A56}', Extend),
+    ('\u{11A57}', '\u{11A58}', SpacingMark),
+    ('\u{11A59}', '\u{11A5B}', Extend),
+    ('\u{11A84}', '\u{11A89}', Prepend),
+    ('\u{11A8A}', '\u{11A96}', Extend),
+    ('\u{11A97}', '\u{11A97}', SpacingMark),
+    ('\u{11A98}', '\u{11A99}', Extend),
+    ('\u{11C2F}', '\u{11C2F}', SpacingMark),
+    ('\u{11C30}', '\u{11C36}', Extend),
+    ('\u{11C38}', '\u{11C3D}', Extend),
+    ('\u{11C3E}', '\u{11C3E}', SpacingMark),
+    ('\u{11C3F}', '\u{11C3F}', Extend),
+    ('\u{11C92}', '\u{11CA7}', Extend),
+    ('\u{11CA9}', '\u{11CA9}', SpacingMark),
+    ('\u{11CAA}', '\u{11CB0}', Extend),
+    ('\u{11CB1}', '\u{11CB1}', SpacingMark),
+    ('\u{11CB2}', '\u{11CB3}', Extend),
+    ('\u{11CB4}', '\u{11CB4}', SpacingMark),
+    ('\u{11CB5}', '\u{11CB6}', Extend),
+    ('\u{11D31}', '\u{11D36}', Extend),
+    ('\u{11D3A}', '\u{11D3A}', Extend),
+    ('\u{11D3C}', '\u{11D3D}', Extend),
+    ('\u{11D3F}', '\u{11D45}', Extend),
+    ('\u{11D46}', '\u{11D46}', Prepend),
+    ('\u{11D47}', '\u{11D47}', Extend),
+    ('\u{11D8A}', '\u{11D8E}', SpacingMark),
+    ('\u{11D90}', '\u{11D91}', Extend),
+    ('\u{11D93}', '\u{11D94}', SpacingMark),
+    ('\u{11D95}', '\u{11D95}', Extend),
+    ('\u{11D96}', '\u{11D96}', SpacingMark),
+    ('\u{11D97}', '\u{11D97}', Extend),
+    ('\u{11EF3}', '\u{11EF4}', Extend),
+    ('\u{11EF5}', '\u{11EF6}', SpacingMark),
+    ('\u{13430}', '\u{13438}', Control),
+    ('\u{16AF0}', '\u{16AF4}', Extend),
+    ('\u{16B30}', '\u{16B36}', Extend),
+    ('\u{16F4F}', '\u{16F4F}', Extend),
+    ('\u{16F51}', '\u{16F87}', SpacingMark),
+    ('\u{16F8F}', '\u{16F92}', Extend),
+    ('\u{16FE4}', '\u{16FE4}', Extend),
+    ('\u{16FF0}', '\u{16FF1}', SpacingMark),
+    ('\u{1BC9D}', '\u{1BC9E}', Extend),
+    ('\u{1BCA0}', '\u{1BCA3}', Control),
+    ('\u{1CF00}', '\u{1CF2D}', Extend),
+    ('\u{1CF30}', '\u{1CF46}', Extend),
+    ('\u{1D165}', '\u{1D165}', Extend),
+    ('\u{1D166}', '\u{1D166}', SpacingMark),
+    ('\u{1D167}', '\u{1D169}', Extend),
+    ('\u{1D16D}', '\u{1D16D}', SpacingMark),
+    ('\u{1D16E}', '\u{1D172}', Extend),
+    ('\u{1D173}', '\u{1D17A}', Control),
+    ('\u{1D17B}', '\u{1D182}', Extend),
+    ('\u{1D185}', '\u{1D18B}', Extend),
+    ('\u{1D1AA}', '\u{1D1AD}', Extend),
+    ('\u{1D242}', '\u{1D244}', Extend),
+    ('\u{1DA00}', '\u{1DA36}', Extend),
+    ('\u{1DA3B}', '\u{1DA6C}', Extend),
+    ('\u{1DA75}', '\u{1DA75}', Extend),
+    ('\u{1DA84}', '\u{1DA84}', Extend),
+    ('\u{1DA9B}', '\u{1DA9F}', Extend),
+    ('\u{1DAA1}', '\u{1DAAF}', Extend),
+    ('\u{1E000}', '\u{1E006}', Extend),
+    ('\u{1E008}', '\u{1E018}', Extend),
+    ('\u{1E01B}', '\u{1E021}', Extend),
+    ('\u{1E023}', '\u{1E024}', Extend),
+    ('\u{1E026}', '\u{1E02A}', Extend),
+    ('\u{1E130}', '\u{1E136}', Extend),
+    ('\u{1E2AE}', '\u{1E2AE}', Extend),
+    ('\u{1E2EC}', '\u{1E2EF}', Extend),
+    ('\u{1E8D0}', '\u{1E8D6}', Extend),
+    ('\u{1E944}', '\u{1E94A}', Extend),
+    ('\u{1F000}', '\u{1F0FF}', ExtendedPictographic),
+    ('\u{1F10D}', '\u{1F10F}', ExtendedPictographic),
+    ('\u{1F12F}', '\u{1F12F}', ExtendedPictographic),
+    ('\u{1F16C}', '\u{1F171}', ExtendedPictographic),
+    ('\u{1F17E}', '\u{1F17F}', ExtendedPictographic),
+    ('\u{1F18E}', '\u{1F18E}', ExtendedPictographic),
+    ('\u{1F191}', '\u{1F19A}', ExtendedPictographic),
+    ('\u{1F1AD}', '\u{1F1E5}', ExtendedPictographic),
+    ('\u{1F1E6}', '\u{1F1FF}', RegionalIndicator),
+    ('\u{1F201}', '\u{1F20F}', ExtendedPictographic),
+    ('\u{1F21A}', '\u{1F21A}', ExtendedPictographic),
+    ('\u{1F22F}', '\u{1F22F}', ExtendedPictographic),
+    ('\u{1F232}', '\u{1F23A}', ExtendedPictographic),
+    ('\u{1F23C}', '\u{1F23F}', ExtendedPictographic),
+    ('\u{1F249}', '\u{1F3FA}', ExtendedPictographic),
+    ('\u{1F3FB}', '\u{1F3FF}', Extend),
+    ('\u{1F400}', '\u{1F53D}', ExtendedPictographic),
+    ('\u{1F546}', '\u{1F64F}', ExtendedPictographic),
+    ('\u{1F680}', '\u{1F6FF}', ExtendedPictographic),
+    ('\u{1F774}', '\u{1F77F}', ExtendedPictographic),
+    ('\u{1F7D5}', '\u{1F7FF}', ExtendedPictographic),
+    ('\u{1F80C}', '\u{1F80F}', ExtendedPictographic),
+    ('\u{1F848}', '\u{1F84F}', ExtendedPictographic),
+    ('\u{1F85A}', '\u{1F85F}', ExtendedPictographic),
+    ('\u{1F888}', '\u{1F88F}', ExtendedPictographic),
+    ('\u{1F8AE}', '\u{1F8FF}', ExtendedPictographic),
+    ('\u{1F90C}', '\u{1F93A}', ExtendedPictographic),
+    ('\u{1F93C}', '\u{1F945}', ExtendedPictographic),
+    ('\u{1F947}', '\u{1FAFF}', ExtendedPictographic),
+    ('\u{1FC00}', '\u{1FFFD}', ExtendedPictographic),
+    ('\u{E0001}', '\u{E0001}', Control),
+    ('\u{E0020}', '\u{E007F}', Extend),
+    ('\u{E0100}', '\u{E01EF}', Extend),
+];
+
+fn gcb(c: char) -> Gcb {
+    match c {
+        ' '..='~' => Other,
+        '\u{AC00}'..='\u{D7A3}' => {
+            if (c as u32 - 0xAC00) % 28 == 0 {
+                Lv
+            } else {
+                Lvt
+            }
+        }
+        _ => GRAPHEME_BREAK
+            .binary_search_by(|&(start, end, _)| {
+                if end < c {
+                    std::cmp::Ordering::Less
+                } else if start > c {
+                    std::cmp::Ordering::Greater
+                } else {
+                    std::cmp::Ordering::Equal
+                }
+            })
+            .map_or(Other, |i| GRAPHEME_BREAK[i].2),
+    }
+}
+
+/// Iterator over the extended grapheme clusters of a string
+///
+/// Created by [`graphemes`].
+#[derive(Debug, Clone)]
+pub struct Graphemes<'a> {
+    text: &'a str,
+}
+
+impl<'a> Iterator for Graphemes<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let mut chars = self.text.char_indices();
+        let (_, first) = chars.next()?;
+        let mut prev = gcb(first);
+        // GB11: inside ExtPict Extend* ZWJ?
+        let mut emoji = prev == ExtendedPictographic;
+        // GB12/13: number of regional indicators seen in this cluster
+        let mut regional = usize::from(prev == RegionalIndicator);
+        let mut end = self.text.len();
+
+        for (i, c) in chars {
+            let next = gcb(c);
+            let joins = match (prev, next) {
+                (Cr, Lf) => true,
+                (Cr | Lf | Control, _) | (_, Cr | Lf | Control) => false,
+                (L, L | V | Lv | Lvt) | (Lv | V, V | T) | (Lvt | T, T) => true,
+                (_, Extend | Zwj | SpacingMark) | (Prepend, _) => true,
+                (Zwj, ExtendedPictographic) => emoji,
+                (RegionalIndicator, RegionalIndicator) => regional % 2 == 1,
+                _ => false,
+            };
+            if !joins {
+                end = i;
+                break;
+            }
+            emoji = match next {
+                ExtendedPictographic => true,
+                Extend | Zwj => emoji,
+                _ => false,
+            };
+            if next == RegionalIndicator {
+                regional += 1;
+            }
+            prev = next;
+        }
+
+        let (cluster, rest) = self.text.split_at(end);
+        self.text = rest;
+        Some(cluster)
+    }
+}
+
+/// Split text into extended grapheme clusters (UAX #29)
+///
+/// Uses a built-in Unicode 14.0 property table. A base letter and its combining
+/// marks, an emoji ZWJ sequence, or a flag's regional indicator pair each form
+/// one cluster.
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::graphemes;
+///
+/// let clusters: Vec<&str> = graphemes("e\u{0301}👍🏽🇺🇸").collect();
+/// assert_eq!(clusters, ["e\u{0301}", "👍🏽", "🇺🇸"]);
+/// ```
+pub fn graphemes(text: &str) -> Graphemes<'_> {
+    Graphemes { text }
+}
+
+/// Whether `c` takes up no columns on its own: controls, format characters and
+/// combining marks
+pub(crate) fn is_zero_width(c: char) -> bool {
+    matches!(gcb(c), Control | Cr | Lf | Extend | Zwj)
+}
+
+/// Whether `c` marks its cluster as an emoji sequence that must not be styled
+///
+/// Covers ZWJ sequences, skin-tone modifiers, emoji presentation (VS16),
+/// keycaps, flags and tag sequences.
+pub(crate) fn is_emoji_component(c: char) -> bool {
+    matches!(
+        c,
+        '\u{200D}'
+            | '\u{FE0F}'
+            | '\u{20E3}'
+            | '\u{1F3FB}'..='\u{1F3FF}'
+            | '\u{1F1E6}'..='\u{1F1FF}'
+            | '\u{E0020}'..='\u{E007F}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(text: &str) -> Vec<&str> {
+        graphemes(text).collect()
+    }
+
+    #[test]
+    fn test_combining_marks() {
+        assert_eq!(split("a\u{0301}\u{0336}b"), ["a\u{0301}\u{0336}", "b"]);
+        assert_eq!(split("\r\n\n"), ["\r\n", "\n"]);
+    }
+
+    #[test]
+    fn test_emoji_sequences() {
+        assert_eq!(split("👨‍👩‍👧x"), ["👨‍👩‍👧", "x"]);
+        assert_eq!(
+            split("❤\u{FE0F}1\u{FE0F}\u{20E3}"),
+            ["❤\u{FE0F}", "1\u{FE0F}\u{20E3}"]
+        );
+        // A ZWJ after a non-pictographic character does not join
+        assert_eq!(split("a\u{200D}👍"), ["a\u{200D}", "👍"]);
+    }
+
+    #[test]
+    fn test_regional_indicator_pairs() {
+        assert_eq!(split("🇺🇸🇫🇷🇩"), ["🇺🇸", "🇫🇷", "🇩"]);
+    }
+
+    #[test]
+    fn test_hangul_and_prepend() {
+        assert_eq!(
+            split("\u{1100}\u{1161}\u{11A8}한"),
+            ["\u{1100}\u{1161}\u{11A8}", "한"]
+        );
+        assert_eq!(split("\u{0600}1"), ["\u{0600}1"]);
+    }
+}
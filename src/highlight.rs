@@ -0,0 +1,149 @@
+use crate::charmap::{push_cluster, CharMap};
+use crate::grapheme::graphemes;
+use std::ops::Range;
+
+/// Style grapheme clusters chosen by a callback
+///
+/// The callback receives each cluster's byte offset and first character, and
+/// returns the style to apply, or `None` to leave it unchanged. Each run of
+/// clusters given a reversed style such as
+/// [`Style::Inverted`](crate::Style::Inverted) is written backwards, as
+/// [`style`](crate::style) writes a whole text.
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{style_by, Style};
+///
+/// let alternating = style_by("abcd", |i, _| (i % 2 == 0).then_some(Style::MathBold));
+/// assert_eq!(alternating, "𝐚b𝐜d");
+/// ```
+pub fn style_by<M, F>(text: &str, mut choose: F) -> String
+where
+    M: CharMap,
+    F: FnMut(usize, char) -> Option<M>,
+{
+    let mut out = String::with_capacity(text.len() * 4);
+    let mut offset = 0;
+    // Separator of the map that styled the previous cluster
+    let mut joined: Option<char> = None;
+    // Clusters given a reversed style, in input order, until the run ends
+    let mut reversed: Vec<String> = Vec::new();
+    for cluster in graphemes(text) {
+        let base = cluster.chars().next().unwrap_or_default();
+        let map = choose(offset, base);
+        offset += cluster.len();
+        if let Some(map) = map.as_ref().filter(|map| map.reversed()) {
+            let mut piece = String::new();
+            if !push_cluster(map, cluster, map.accent_policy(), &mut piece) {
+                piece.push_str(cluster);
+            }
+            reversed.push(piece);
+            joined = None;
+            continue;
+        }
+        out.extend(reversed.drain(..).rev());
+
+        let start = out.len();
+        // `Some(separator)` when the cluster was styled
+        let styled = map.and_then(|map| {
+            let separator = map.separator();
+            if let Some(sep) = separator.filter(|&sep| joined == Some(sep)) {
+                out.push(sep);
+            }
+            if push_cluster(&map, cluster, map.accent_policy(), &mut out) {
+                Some(separator)
+            } else {
+                out.truncate(start);
+                None
+            }
+        });
+        if styled.is_none() {
+            out.push_str(cluster);
+        }
+        joined = styled.flatten();
+    }
+    out.extend(reversed.drain(..).rev());
+    out
+}
+
+/// Style the grapheme clusters starting within a byte range
+///
+/// Characters and clusters are never split: a cluster is styled when its first
+/// byte lies in `range`. Ranges past the end of `text` are clamped.
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{style_range, Style};
+///
+/// assert_eq!(style_range("find me", 5..7, Style::MathBold), "find 𝐦𝐞");
+/// // 'é' starts at byte 3 and spans two bytes, so it is styled whole
+/// assert_eq!(style_range("café!", 2..4, Style::MathBold), "ca𝐟𝐞\u{0301}!");
+/// ```
+pub fn style_range<M: CharMap>(text: &str, range: Range<usize>, style: M) -> String {
+    style_by(text, |offset, _| range.contains(&offset).then_some(&style))
+}
+
+/// Style the grapheme clusters whose first character matches a predicate
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{style_where, Style};
+///
+/// assert_eq!(style_where("v1.2.3", |c| c.is_ascii_digit(), Style::Circled), "v①.②.③");
+/// ```
+pub fn style_where<M, P>(text: &str, mut predicate: P, style: M) -> String
+where
+    M: CharMap,
+    P: FnMut(char) -> bool,
+{
+    style_by(text, |_, c| predicate(c).then_some(&style))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{style, Style};
+
+    #[test]
+    fn test_style_by_offsets_are_bytes() {
+        let mut seen = Vec::new();
+        style_by("aé b", |i, c| {
+            seen.push((i, c));
+            None::<Style>
+        });
+        assert_eq!(seen, vec![(0, 'a'), (1, 'é'), (3, ' '), (4, 'b')]);
+    }
+
+    #[test]
+    fn test_style_range_multibyte() {
+        assert_eq!(style_range("éa", 0..2, Style::MathBold), "𝐞\u{0301}a");
+        assert_eq!(style_range("ab", 1..100, Style::MathBold), "a𝐛");
+        assert_eq!(style_range("ab", 5..9, Style::MathBold), "ab");
+    }
+
+    #[test]
+    fn test_style_range_keeps_clusters_whole() {
+        // The combining accent belongs to the 'e' cluster at offset 0
+        assert_eq!(
+            style_range("e\u{0301}x", 0..1, Style::Strikethrough),
+            "e\u{0301}\u{0336}x"
+        );
+    }
+
+    #[test]
+    fn test_reversed_runs() {
+        let out = style_range("ab cd!", 0..5, Style::Inverted);
+        assert_eq!(out, style("ab cd", Style::Inverted) + "!");
+        let out = style_where("ab-cd", char::is_alphabetic, Style::Inverted);
+        assert_eq!(out, "qɐ-pɔ");
+    }
+
+    #[test]
+    fn test_style_where() {
+        let out = style_where("Hello World", char::is_uppercase, Style::MathBold);
+        assert_eq!(out, "𝐇ello 𝐖orld");
+    }
+}
@@ -0,0 +1,331 @@
+use crate::compose::{apply_effects, Effect};
+use crate::styles::{style, Style};
+use std::ops::Range;
+
+/// A tag [`html_to_unicode`] did not recognize and removed
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StrippedTag {
+    /// Lowercase tag name, without `<`, `/` or attributes
+    pub name: String,
+    /// Byte range of the whole tag in the input
+    pub range: Range<usize>,
+}
+
+/// Output of [`html_to_unicode`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtmlText {
+    /// The converted text
+    pub text: String,
+    /// Every unknown opening, closing or self-closing tag, in input order
+    pub stripped: Vec<StrippedTag>,
+}
+
+/// Convert a small subset of HTML to styled plain text
+///
+/// | Tags | Result |
+/// |------|--------|
+/// | `<b>`, `<strong>` | [`Style::MathBold`] |
+/// | `<i>`, `<em>` | [`Style::Italic`] |
+/// | both nested | [`Style::BoldItalic`] |
+/// | `<code>` | [`Style::Monospace`] |
+/// | `<sup>`, `<sub>` | [`Style::Superscript`], [`Style::Subscript`] |
+/// | `<s>`, `<u>` | [`Effect::Strikethrough`], [`Effect::Underline`] |
+/// | `<br>` | line break |
+///
+/// `<code>` takes precedence over `<sup>` and `<sub>`, which take precedence
+/// over bold and italic. Character references (`&amp;`, `&#169;`, `&#xA9;`)
+/// are decoded, comments are dropped and whitespace is kept as written. Other
+/// tags are removed, keeping their content, and listed in
+/// [`HtmlText::stripped`].
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::html_to_unicode;
+///
+/// let result = html_to_unicode("<b>Tom &amp; <i>Jerry</i></b> <span>x</span>");
+/// assert_eq!(result.text, "𝐓𝐨𝐦 & 𝑱𝒆𝒓𝒓𝒚 x");
+/// assert_eq!(result.stripped[0].name, "span");
+/// ```
+pub fn html_to_unicode(html: &str) -> HtmlText {
+    let mut state = Formatting::default();
+    let mut text = String::with_capacity(html.len() * 4);
+    let mut stripped = Vec::new();
+    let mut pos = 0;
+    while pos < html.len() {
+        let rest = &html[pos..];
+        if rest.starts_with("<!--") {
+            pos = rest.find("-->").map_or(html.len(), |end| pos + end + 3);
+            continue;
+        }
+        if let Some(tag) = Tag::parse(html, pos) {
+            if tag.name == "br" {
+                text.push('\n');
+            } else if !state.apply(&tag) {
+                stripped.push(StrippedTag {
+                    name: tag.name,
+                    range: pos..tag.end,
+                });
+            }
+            pos = tag.end;
+            continue;
+        }
+        let skip = usize::from(rest.starts_with('<'));
+        let end = rest[skip..]
+            .find('<')
+            .map_or(html.len(), |i| pos + skip + i);
+        text.push_str(&state.render(&decode_entities(&html[pos..end])));
+        pos = end;
+    }
+    HtmlText { text, stripped }
+}
+
+/// Nesting depth of each supported tag
+#[derive(Debug, Clone, Copy, Default)]
+struct Formatting {
+    bold: u32,
+    italic: u32,
+    code: u32,
+    sup: u32,
+    sub: u32,
+    strike: u32,
+    underline: u32,
+}
+
+impl Formatting {
+    /// Update depths for a tag, returning `false` if the tag is unknown
+    fn apply(&mut self, tag: &Tag) -> bool {
+        let depth = match tag.name.as_str() {
+            "b" | "strong" => &mut self.bold,
+            "i" | "em" => &mut self.italic,
+            "code" => &mut self.code,
+            "sup" => &mut self.sup,
+            "sub" => &mut self.sub,
+            "s" => &mut self.strike,
+            "u" => &mut self.underline,
+            _ => return false,
+        };
+        if tag.self_closing {
+            // `<b/>` opens and closes nothing
+        } else if tag.closing {
+            // A stray closing tag is ignored
+            *depth = depth.saturating_sub(1);
+        } else {
+            *depth += 1;
+        }
+        true
+    }
+
+    fn style(&self) -> Option<Style> {
+        if self.code > 0 {
+            return Some(Style::Monospace);
+        }
+        if self.sup > 0 {
+            return Some(Style::Superscript);
+        }
+        if self.sub > 0 {
+            return Some(Style::Subscript);
+        }
+        match (self.bold > 0, self.italic > 0) {
+            (true, true) => Some(Style::BoldItalic),
+            (true, false) => Some(Style::MathBold),
+            (false, true) => Some(Style::Italic),
+            (false, false) => None,
+        }
+    }
+
+    fn render(&self, text: &str) -> String {
+        let styled = match self.style() {
+            Some(s) => style(text, s),
+            None => text.to_string(),
+        };
+        let mut effects = Vec::new();
+        if self.strike > 0 {
+            effects.push(Effect::Strikethrough);
+        }
+        if self.underline > 0 {
+            effects.push(Effect::Underline);
+        }
+        apply_effects(&styled, &effects)
+    }
+}
+
+/// An opening, closing or self-closing tag
+struct Tag {
+    name: String,
+    closing: bool,
+    self_closing: bool,
+    /// Byte offset just past the `>`
+    end: usize,
+}
+
+impl Tag {
+    /// Parse a tag at `pos`, or return `None` if the `<` there is plain text
+    fn parse(html: &str, pos: usize) -> Option<Tag> {
+        let rest = html[pos..].strip_prefix('<')?;
+        let (closing, rest) = match rest.strip_prefix('/') {
+            Some(rest) => (true, rest),
+            None => (false, rest),
+        };
+        if !rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return None;
+        }
+        let name_len = rest
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '-')
+            .unwrap_or(rest.len());
+        let name = rest[..name_len].to_ascii_lowercase();
+
+        // Skip attributes, honoring quoted values that may contain '>'
+        let mut quote = None;
+        for (i, c) in rest[name_len..].char_indices() {
+            match (quote, c) {
+                (Some(q), _) if c == q => quote = None,
+                (Some(_), _) => {}
+                (None, '"' | '\'') => quote = Some(c),
+                (None, '>') => {
+                    let attrs = &rest[name_len..name_len + i];
+                    let end = html.len() - rest.len() + name_len + i + 1;
+                    return Some(Tag {
+                        name,
+                        closing,
+                        self_closing: attrs.trim_end().ends_with('/'),
+                        end,
+                    });
+                }
+                (None, '<') => return None,
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+/// Named character references that commonly appear in CMS output
+const ENTITIES: &[(&str, char)] = &[
+    ("amp", '&'),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("quot", '"'),
+    ("apos", '\''),
+    ("nbsp", '\u{00A0}'),
+    ("copy", '©'),
+    ("reg", '®'),
+    ("trade", '™'),
+    ("deg", '°'),
+    ("times", '×'),
+    ("divide", '÷'),
+    ("plusmn", '±'),
+    ("middot", '·'),
+    ("bull", '•'),
+    ("hellip", '…'),
+    ("ndash", '–'),
+    ("mdash", '—'),
+    ("lsquo", '‘'),
+    ("rsquo", '’'),
+    ("ldquo", '“'),
+    ("rdquo", '”'),
+    ("laquo", '«'),
+    ("raquo", '»'),
+    ("euro", '€'),
+    ("pound", '£'),
+    ("yen", '¥'),
+    ("cent", '¢'),
+    ("sect", '§'),
+    ("para", '¶'),
+];
+
+/// Longest entity between `&` and `;` that [`decode_entities`] decodes
+const MAX_ENTITY_LEN: usize = 32;
+
+/// Decode character references, leaving unknown or malformed ones as written
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        // Only look as far as the longest entity could reach, so a stray `&`
+        // does not scan the rest of the text
+        let semicolon = rest.as_bytes()[1..]
+            .iter()
+            .take(MAX_ENTITY_LEN + 1)
+            .position(|&b| b == b';');
+        let decoded = semicolon.and_then(|len| {
+            let name = &rest[1..1 + len];
+            let c = match name.strip_prefix('#') {
+                Some(num) => match num.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => num.parse().ok(),
+                }
+                .and_then(char::from_u32),
+                None => ENTITIES.iter().find(|(n, _)| *n == name).map(|&(_, c)| c),
+            }?;
+            Some((c, len + 2))
+        });
+        match decoded {
+            Some((c, len)) => {
+                out.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(html: &str) -> String {
+        html_to_unicode(html).text
+    }
+
+    #[test]
+    fn test_nested_tags_combine() {
+        assert_eq!(text("<b><i>ab</i></b>"), "𝒂𝒃");
+        assert_eq!(text("<EM>a<Strong>b</Strong></EM>c"), "𝑎𝒃c");
+        assert_eq!(text("<b>x<code>y</code></b>"), "𝐱𝚢");
+        assert_eq!(text("E=mc<sup>2</sup>, H<sub>2</sub>O"), "E=mc², H₂O");
+    }
+
+    #[test]
+    fn test_effects() {
+        assert_eq!(text("<s>no</s>"), "n\u{0336}o\u{0336}");
+        assert_eq!(text("<u><b>a b</b></u>"), "𝐚\u{0332} 𝐛\u{0332}");
+    }
+
+    #[test]
+    fn test_entities() {
+        assert_eq!(
+            text("&lt;b&gt; &copy;&#174;&#x2122; &bogus; & &#xZZ;"),
+            "<b> ©®™ &bogus; & &#xZZ;"
+        );
+        // Entities decode before styling, so '&' stays unstyled
+        assert_eq!(text("<b>A&amp;B</b>"), "𝐀&𝐁");
+        // A `;` too far away is not part of an entity
+        let far = format!("&#{}65;", "0".repeat(40));
+        assert_eq!(decode_entities(&far), far);
+        assert_eq!(decode_entities(&"&".repeat(1000)), "&".repeat(1000));
+    }
+
+    #[test]
+    fn test_unknown_tags_reported() {
+        let html = "<p class=\"a>b\">Hi<br/>there</p><!-- <b> -->";
+        let result = html_to_unicode(html);
+        assert_eq!(result.text, "Hi\nthere");
+        let names: Vec<_> = result.stripped.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["p", "p"]);
+        assert_eq!(&html[result.stripped[0].range.clone()], "<p class=\"a>b\">");
+    }
+
+    #[test]
+    fn test_stray_markup_is_text() {
+        assert_eq!(text("1 < 2 and </b> 3 <4"), "1 < 2 and  3 <4");
+        assert_eq!(text("</i>x<i>y"), "x𝑦");
+    }
+}
@@ -0,0 +1,582 @@
+use crate::canvas::{BlockCanvas, BlockMode, BrailleCanvas, Canvas};
+use crate::cells::glyph_char;
+use std::fmt;
+
+/// What went wrong while reading a PGM or PPM image
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageErrorKind {
+    /// The data does not start with `P2`, `P3`, `P5` or `P6`
+    UnsupportedFormat,
+    /// The width, height or maximum value is missing, zero or too large
+    InvalidHeader,
+    /// The data ends before every sample is read
+    Truncated,
+    /// A sample is not a number or exceeds the maximum value
+    InvalidSample,
+}
+
+/// Error returned by [`Image::from_pnm`] for data it cannot read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageError {
+    /// What was wrong with the data
+    pub kind: ImageErrorKind,
+    /// Byte offset in the data where the problem was found
+    pub offset: usize,
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ImageErrorKind::UnsupportedFormat => f.write_str("Not a PGM or PPM image")?,
+            ImageErrorKind::InvalidHeader => f.write_str("Invalid image header")?,
+            ImageErrorKind::Truncated => f.write_str("Image data ends early")?,
+            ImageErrorKind::InvalidSample => f.write_str("Invalid sample")?,
+        }
+        write!(f, " at byte {}", self.offset)
+    }
+}
+
+impl std::error::Error for ImageError {}
+
+/// A grayscale raster image, 0 for black to 255 for white
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    /// Pixels row by row
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    /// Image from grayscale pixels, row by row
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pixels` does not hold `width * height` values.
+    pub fn from_gray(width: usize, height: usize, pixels: Vec<u8>) -> Self {
+        assert_eq!(pixels.len(), width * height, "pixel count");
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Read a PGM (`P2`, `P5`) or PPM (`P3`, `P6`) image
+    ///
+    /// Both the plain-text and binary variants are read, with 8- or 16-bit
+    /// samples. Colors are converted to gray by luminance, and data after the
+    /// first image is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ImageError`] if the data is not a well-formed image.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use prettychars::Image;
+    ///
+    /// let image = Image::from_pnm(b"P2\n# a comment\n2 1\n4\n0 4\n").unwrap();
+    /// assert_eq!((image.width(), image.height()), (2, 1));
+    /// assert_eq!((image.get(0, 0), image.get(1, 0)), (0, 255));
+    /// ```
+    pub fn from_pnm(data: &[u8]) -> Result<Image, ImageError> {
+        let (binary, channels) = match data.get(..2) {
+            Some(b"P2") => (false, 1),
+            Some(b"P3") => (false, 3),
+            Some(b"P5") => (true, 1),
+            Some(b"P6") => (true, 3),
+            _ => return Err(error(ImageErrorKind::UnsupportedFormat, 0)),
+        };
+        let mut reader = Reader { data, pos: 2 };
+        let width = reader.header()?;
+        let height = reader.header()?;
+        let max = reader.header()?;
+        if max > 65535 {
+            return Err(error(ImageErrorKind::InvalidHeader, reader.pos));
+        }
+        let count = (width as usize)
+            .checked_mul(height as usize)
+            .filter(|&n| n.checked_mul(channels * 2).is_some())
+            .ok_or(error(ImageErrorKind::InvalidHeader, reader.pos))?;
+        if binary {
+            // Exactly one whitespace byte separates the header from the raster
+            if !reader
+                .data
+                .get(reader.pos)
+                .is_some_and(u8::is_ascii_whitespace)
+            {
+                return Err(error(ImageErrorKind::Truncated, reader.pos));
+            }
+            reader.pos += 1;
+            // Check the raster is all there before allocating for it
+            let bytes = count * channels * if max > 255 { 2 } else { 1 };
+            if data.len() - reader.pos < bytes {
+                return Err(error(ImageErrorKind::Truncated, data.len()));
+            }
+        }
+
+        // Plain samples take at least a byte each, so the data bounds the size
+        let mut pixels = Vec::with_capacity(count.min(data.len() - reader.pos));
+        let mut sample = [0; 3];
+        for _ in 0..count {
+            for value in &mut sample[..channels] {
+                let raw = if binary {
+                    reader.binary(max > 255)?
+                } else {
+                    reader.number(ImageErrorKind::InvalidSample)?
+                };
+                if raw > max {
+                    return Err(error(ImageErrorKind::InvalidSample, reader.pos));
+                }
+                *value = (raw * 255 + max / 2) / max;
+            }
+            pixels.push(match channels {
+                1 => sample[0] as u8,
+                // Rec. 601 luma
+                _ => ((299 * sample[0] + 587 * sample[1] + 114 * sample[2] + 500) / 1000) as u8,
+            });
+        }
+        Ok(Image::from_gray(width as usize, height as usize, pixels))
+    }
+
+    /// Width in pixels
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height in pixels
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Gray value of the pixel at `x`, `y`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pixel is outside the image.
+    pub fn get(&self, x: usize, y: usize) -> u8 {
+        assert!(x < self.width && y < self.height, "pixel outside image");
+        self.pixels[y * self.width + x]
+    }
+
+    /// Brightness from 0.0 to 1.0 of `width` × `height` pixels, each the
+    /// average of the source pixels it covers
+    fn resize(&self, width: usize, height: usize) -> Vec<f32> {
+        // Source pixels from `first` up to `end` cover target pixel `i` of `n`
+        let span = |i: usize, n: usize, size: usize| {
+            let first = i * size / n;
+            (first, (((i + 1) * size + n - 1) / n).max(first + 1))
+        };
+        let mut out = Vec::with_capacity(width * height);
+        for y in 0..height {
+            let (top, bottom) = span(y, height, self.height);
+            for x in 0..width {
+                let (left, right) = span(x, width, self.width);
+                let mut sum = 0u64;
+                for row in top..bottom {
+                    let start = row * self.width;
+                    sum += self.pixels[start + left..start + right]
+                        .iter()
+                        .map(|&p| u64::from(p))
+                        .sum::<u64>();
+                }
+                let count = ((bottom - top) * (right - left)) as f32;
+                out.push(sum as f32 / count / 255.0);
+            }
+        }
+        out
+    }
+}
+
+fn error(kind: ImageErrorKind, offset: usize) -> ImageError {
+    ImageError { kind, offset }
+}
+
+/// Cursor over the bytes of a PNM file
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    /// A nonzero header number
+    fn header(&mut self) -> Result<u32, ImageError> {
+        match self.number(ImageErrorKind::InvalidHeader)? {
+            0 => Err(error(ImageErrorKind::InvalidHeader, self.pos)),
+            n => Ok(n),
+        }
+    }
+
+    /// A decimal number after whitespace and `#` comments
+    fn number(&mut self, invalid: ImageErrorKind) -> Result<u32, ImageError> {
+        loop {
+            match self.data.get(self.pos) {
+                Some(b) if b.is_ascii_whitespace() => self.pos += 1,
+                Some(b'#') => {
+                    while self.data.get(self.pos).is_some_and(|&b| b != b'\n') {
+                        self.pos += 1;
+                    }
+                }
+                Some(_) => break,
+                None => return Err(error(ImageErrorKind::Truncated, self.pos)),
+            }
+        }
+        let start = self.pos;
+        let mut value: u32 = 0;
+        while let Some(digit) = self.data.get(self.pos).filter(|b| b.is_ascii_digit()) {
+            value = value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add(u32::from(digit - b'0')))
+                .ok_or(error(invalid, start))?;
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return Err(error(invalid, start));
+        }
+        Ok(value)
+    }
+
+    /// A big-endian sample of one or two bytes
+    fn binary(&mut self, wide: bool) -> Result<u32, ImageError> {
+        let len = if wide { 2 } else { 1 };
+        let bytes = self
+            .data
+            .get(self.pos..self.pos + len)
+            .ok_or(error(ImageErrorKind::Truncated, self.pos))?;
+        self.pos += len;
+        Ok(bytes.iter().fold(0, |v, &b| v << 8 | u32::from(b)))
+    }
+}
+
+/// Characters [`ImageRender`] draws pixels with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ImageMode {
+    /// One pixel per character, from blank through `shade.light`,
+    /// `shade.medium` and `shade.dark` to `block.full`
+    #[default]
+    Shades,
+
+    /// Several on/off pixels per character with a [`BlockCanvas`]
+    Blocks(BlockMode),
+
+    /// 2×4 on/off pixels per character with a [`BrailleCanvas`]
+    Braille,
+}
+
+impl ImageMode {
+    /// Pixels across and down each character
+    fn cell_size(self) -> (usize, usize) {
+        match self {
+            ImageMode::Shades => (1, 1),
+            ImageMode::Blocks(mode) => mode.cell_size(),
+            ImageMode::Braille => (2, 4),
+        }
+    }
+}
+
+/// How [`ImageRender`] spreads the error of drawing a gray with fewer levels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Dither {
+    /// Round every pixel to the nearest level
+    None,
+
+    /// Pass each pixel's error on to its neighbours (Floyd–Steinberg), for
+    /// smooth gradients and photos
+    #[default]
+    FloydSteinberg,
+
+    /// Compare pixels against a 4×4 Bayer matrix, for a regular pattern that
+    /// stays stable when the image changes slightly
+    Ordered,
+}
+
+/// 4×4 Bayer threshold matrix
+const BAYER: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+impl Dither {
+    /// Quantize brightness values in place to levels `0..levels`
+    fn apply(self, values: &mut [f32], width: usize, levels: usize) -> Vec<u8> {
+        let top = (levels - 1) as f32;
+        let quantize = |v: f32| (v * top).round().clamp(0.0, top);
+        let mut out = Vec::with_capacity(values.len());
+        for i in 0..values.len() {
+            let (x, y) = (i % width, i / width);
+            let level = match self {
+                Dither::None => quantize(values[i]),
+                Dither::Ordered => {
+                    let scaled = (values[i] * top).clamp(0.0, top);
+                    let threshold = (f32::from(BAYER[y % 4][x % 4]) + 0.5) / 16.0;
+                    (scaled.floor() + f32::from(u8::from(scaled.fract() > threshold))).min(top)
+                }
+                Dither::FloydSteinberg => {
+                    let level = quantize(values[i]);
+                    let err = values[i] - level / top;
+                    let height = values.len() / width;
+                    let mut spread = |dx: isize, dy: usize, weight: f32| {
+                        let nx = x as isize + dx;
+                        if nx >= 0 && (nx as usize) < width && y + dy < height {
+                            values[(y + dy) * width + nx as usize] += err * weight / 16.0;
+                        }
+                    };
+                    spread(1, 0, 7.0);
+                    spread(-1, 1, 3.0);
+                    spread(0, 1, 5.0);
+                    spread(1, 1, 1.0);
+                    level
+                }
+            };
+            out.push(level as u8);
+        }
+        out
+    }
+}
+
+/// Most lines [`ImageRender::render`] draws
+const MAX_LINES: usize = 1000;
+
+/// Settings for drawing an [`Image`] as text
+///
+/// The image is scaled to the given number of columns, and to as many lines
+/// as keep its proportions on a terminal whose cells are
+/// [`cell_aspect`](ImageRender::cell_aspect) times taller than wide. Images
+/// that would need over 1000 lines are drawn narrower to fit. Bright
+/// pixels are drawn and dark ones left blank, which suits light text on a dark
+/// background; [`invert`](ImageRender::invert) swaps them.
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{Dither, Image, ImageMode, ImageRender};
+///
+/// let gradient = Image::from_gray(5, 1, vec![0, 64, 128, 191, 255]);
+/// let render = ImageRender::new(ImageMode::Shades)
+///     .columns(5)
+///     .cell_aspect(1.0)
+///     .dither(Dither::None);
+/// assert_eq!(render.render(&gradient), " ░▒▓█");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageRender {
+    mode: ImageMode,
+    dither: Dither,
+    columns: usize,
+    cell_aspect: f32,
+    invert: bool,
+}
+
+impl ImageRender {
+    /// Draw in `mode`, 80 columns wide with Floyd–Steinberg dithering
+    pub fn new(mode: ImageMode) -> Self {
+        ImageRender {
+            mode,
+            dither: Dither::default(),
+            columns: 80,
+            cell_aspect: 2.0,
+            invert: false,
+        }
+    }
+
+    /// Dithering used to reduce gray values to the levels the mode can draw
+    pub fn dither(mut self, dither: Dither) -> Self {
+        self.dither = dither;
+        self
+    }
+
+    /// Width of the output in columns
+    pub fn columns(mut self, columns: usize) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Height of a terminal cell divided by its width, 2.0 by default
+    ///
+    /// An aspect that is not a positive number falls back to the default.
+    pub fn cell_aspect(mut self, aspect: f32) -> Self {
+        self.cell_aspect = aspect;
+        self
+    }
+
+    /// Draw dark pixels and leave bright ones blank, for light backgrounds
+    pub fn invert(mut self, invert: bool) -> Self {
+        self.invert = invert;
+        self
+    }
+
+    /// Draw `image` as lines of text
+    ///
+    /// An empty image, or zero columns, gives an empty string.
+    pub fn render(&self, image: &Image) -> String {
+        if image.width == 0 || image.height == 0 || self.columns == 0 {
+            return String::new();
+        }
+        let (across, down) = self.mode.cell_size();
+        let aspect = match self.cell_aspect {
+            aspect if aspect.is_finite() && aspect > 0.0 => aspect,
+            _ => 2.0,
+        };
+        let lines = image.height as f32 * self.columns as f32 / image.width as f32 / aspect;
+        let (columns, lines) = if lines > MAX_LINES as f32 {
+            let columns = self.columns as f32 * MAX_LINES as f32 / lines;
+            ((columns.round() as usize).max(1), MAX_LINES)
+        } else {
+            (self.columns, (lines.round() as usize).max(1))
+        };
+        let (width, height) = (columns * across, lines * down);
+
+        let mut values = image.resize(width, height);
+        if self.invert {
+            values.iter_mut().for_each(|v| *v = 1.0 - *v);
+        }
+        match self.mode {
+            ImageMode::Shades => {
+                let ramp = [
+                    ' ',
+                    glyph_char("shade.light"),
+                    glyph_char("shade.medium"),
+                    glyph_char("shade.dark"),
+                    glyph_char("block.full"),
+                ];
+                let levels = self.dither.apply(&mut values, width, ramp.len());
+                let rows: Vec<String> = levels
+                    .chunks(width)
+                    .map(|row| row.iter().map(|&l| ramp[usize::from(l)]).collect())
+                    .collect();
+                rows.join("\n")
+            }
+            ImageMode::Blocks(mode) => {
+                let mut canvas = BlockCanvas::new(width, height, mode);
+                self.draw(&mut canvas, &mut values);
+                canvas.render()
+            }
+            ImageMode::Braille => {
+                let mut canvas = BrailleCanvas::new(width, height);
+                self.draw(&mut canvas, &mut values);
+                canvas.render()
+            }
+        }
+    }
+
+    /// Dither brightness values to on/off pixels of `canvas`
+    fn draw(&self, canvas: &mut impl Canvas, values: &mut [f32]) {
+        let width = canvas.width();
+        for (i, level) in self.dither.apply(values, width, 2).into_iter().enumerate() {
+            canvas.set_pixel(i % width, i / width, level == 1);
+        }
+    }
+}
+
+impl Default for ImageRender {
+    fn default() -> Self {
+        ImageRender::new(ImageMode::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kind(data: &[u8]) -> ImageErrorKind {
+        Image::from_pnm(data).unwrap_err().kind
+    }
+
+    #[test]
+    fn test_parse_formats() {
+        let plain = Image::from_pnm(b"P2 3 1 255 0 128 255").unwrap();
+        assert_eq!(plain, Image::from_gray(3, 1, vec![0, 128, 255]));
+        let binary = Image::from_pnm(b"P5\n3 1\n255\n\x00\x80\xFF").unwrap();
+        assert_eq!(binary, plain);
+
+        let color = Image::from_pnm(b"P3 2 1 15 15 0 0 0 0 15").unwrap();
+        assert_eq!(color.pixels, [76, 29]);
+        let wide = Image::from_pnm(b"P6 1 1 65535\n\xFF\xFF\x00\x00\x80\x00").unwrap();
+        assert_eq!(wide.pixels, [91]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(kind(b"P1 1 1 1"), ImageErrorKind::UnsupportedFormat);
+        assert_eq!(kind(b"P2 0 1 255 0"), ImageErrorKind::InvalidHeader);
+        assert_eq!(kind(b"P2 1 1 70000 0"), ImageErrorKind::InvalidHeader);
+        assert_eq!(kind(b"P2 x"), ImageErrorKind::InvalidHeader);
+        assert_eq!(kind(b"P2 2 1 255 0"), ImageErrorKind::Truncated);
+        assert_eq!(kind(b"P5 2 1 255\n\x00"), ImageErrorKind::Truncated);
+        // A huge header must fail before anything is allocated for it
+        assert_eq!(
+            kind(b"P5 1000000000 1000000000 255\n"),
+            ImageErrorKind::Truncated
+        );
+        assert_eq!(
+            kind(b"P2 1000000000 1000000000 255\n0"),
+            ImageErrorKind::Truncated
+        );
+        assert_eq!(kind(b"P2 1 1 15 16"), ImageErrorKind::InvalidSample);
+        let err = Image::from_pnm(b"P2 1 1 15 ?").unwrap_err();
+        assert_eq!(err.to_string(), "Invalid sample at byte 10");
+    }
+
+    #[test]
+    fn test_resize_keeps_aspect() {
+        let image = Image::from_gray(100, 100, vec![255; 10_000]);
+        let shades = ImageRender::new(ImageMode::Shades)
+            .columns(10)
+            .render(&image);
+        assert_eq!(shades.lines().count(), 5);
+        assert!(shades.lines().all(|line| line == "█".repeat(10)));
+        for aspect in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+            let render = ImageRender::new(ImageMode::Shades)
+                .columns(10)
+                .cell_aspect(aspect)
+                .render(&image);
+            assert_eq!(render, shades, "{aspect}");
+        }
+
+        let braille = ImageRender::new(ImageMode::Braille)
+            .columns(4)
+            .render(&image);
+        assert_eq!(braille, "⣿⣿⣿⣿\n⣿⣿⣿⣿");
+        let inverted = ImageRender::new(ImageMode::Braille)
+            .columns(4)
+            .invert(true)
+            .render(&image);
+        assert_eq!(
+            inverted,
+            "\u{2800}".repeat(4) + "\n" + &"\u{2800}".repeat(4)
+        );
+    }
+
+    #[test]
+    fn test_tall_image_is_narrowed() {
+        let image = Image::from_gray(1, 1_000_000, vec![255; 1_000_000]);
+        let render = ImageRender::new(ImageMode::Shades).render(&image);
+        assert_eq!(render.lines().count(), MAX_LINES);
+        assert!(render.lines().all(|line| line == "█"));
+
+        // Half as many columns for twice the lines
+        let image = Image::from_gray(100, 10_000, vec![255; 1_000_000]);
+        let render = ImageRender::new(ImageMode::Braille).render(&image);
+        assert_eq!(render.lines().count(), MAX_LINES);
+        assert!(render.lines().all(|line| line.chars().count() == 20));
+    }
+
+    #[test]
+    fn test_dithering_mid_gray() {
+        let gray = Image::from_gray(8, 8, vec![128; 64]);
+        for dither in [Dither::FloydSteinberg, Dither::Ordered] {
+            let mut canvas = BrailleCanvas::new(16, 16);
+            let render = ImageRender::new(ImageMode::Braille).dither(dither);
+            render.draw(&mut canvas, &mut gray.resize(16, 16));
+            let on = (0..16)
+                .flat_map(|y| (0..16).map(move |x| (x, y)))
+                .filter(|&(x, y)| canvas.pixel(x, y))
+                .count();
+            assert!((120..=136).contains(&on), "{dither:?}: {on}");
+        }
+        let flat = ImageRender::new(ImageMode::Blocks(BlockMode::HalfBlock))
+            .columns(2)
+            .dither(Dither::None)
+            .render(&Image::from_gray(2, 2, vec![255, 0, 0, 255]));
+        assert_eq!(flat, "▀▄");
+    }
+}
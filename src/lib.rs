@@ -3,7 +3,7 @@
 //! Unicode text styling and named glyph lookup with zero runtime overhead.
 //!
 //! This crate provides two main features:
-//! - **Text styling**: Convert text to 24 different Unicode styles (bold, italic, circled, etc.)
+//! - **Text styling**: Convert text to 26 different Unicode styles (bold, italic, circled, etc.)
 //! - **Named glyphs**: Look up 531 Unicode glyphs by name (arrows, box-drawing, stars, etc.)
 //!
 //! ## Quick Start
@@ -24,15 +24,121 @@
 //!
 //! ## Text Styles
 //!
-//! 24 Unicode text styles are available, including:
+//! 26 Unicode text styles are available, including:
 //!
 //! - Mathematical: `MathBold`, `MathItalic`, `DoubleStruck`
 //! - Enclosed: `Circled`, `CircledNegative`, `Squared`, `SquaredNegative`, `Parenthesized`
-//! - Typography: `Fraktur`, `Script`, `SmallCaps`
+//! - Typography: `Fraktur`, `Script`, `SmallCaps`, `PetiteCaps`
 //! - Technical: `Monospace`, `Fullwidth`, `Superscript`, `Subscript`
-//! - Effects: `Strikethrough`, `Inverted`
+//! - Effects: `Strikethrough`, `Inverted`, `RegionalIndicator`
 //!
-//! See [`Style`] for the complete list.
+//! See [`Style`] for the complete list. Styles parse from and display as stable
+//! kebab-case names (`"sans-serif-bold"`), and [`Style::support_risk`] rates how
+//! likely a style is to render as missing glyphs.
+//!
+//! Accented and extended Latin letters (`é`, `ø`, `ß`) are decomposed and
+//! styled per [`Style::accent_policy`].
+//!
+//! Characters a style cannot map pass through unchanged. [`style_with`] takes
+//! [`StyleOptions`] with a [`Fallback`] chain (case folding, alternate styles,
+//! a substitute character) for those gaps. [`Style::supports`] and
+//! [`Style::coverage`] describe what a style maps, and [`try_style`] reports
+//! unmapped characters instead of passing them through.
+//!
+//! Styling works on extended grapheme clusters (see [`graphemes`]), so
+//! existing accents stay attached and emoji sequences pass through intact.
+//! Styled text is longer than it looks: [`TextMetrics`] counts bytes, UTF-16
+//! units, code points, clusters and columns, and [`truncate_to`] shortens text
+//! to a [`Limit`] without splitting clusters.
+//!
+//! To style only part of a string, use [`style_range`] (byte range),
+//! [`style_where`] (character predicate) or [`style_by`] (per-character callback).
+//! [`style_text_smart`] leaves URLs, `@mentions`, `#hashtags` and `` `code` ``
+//! spans untouched so links keep working.
+//!
+//! For input too large to hold in memory, [`StyleWriter`] styles bytes as they
+//! are written to any [`std::io::Write`], optionally leaving ANSI escape
+//! sequences untouched.
+//!
+//! [`zalgo`] stacks seeded, reproducible combining marks on each cluster for
+//! glitch text, and [`dezalgo`] strips them again.
+//!
+//! Screen readers announce styled characters by name. [`Accessible`] pairs
+//! styled text with plain alt text (recoverable with [`unstyle`]), and
+//! [`lint_accessibility`] scores how badly a string will be read aloud.
+//!
+//! ## Inline Markup
+//!
+//! [`render`] builds mixed lines from a template; [`pretty!`] does the same for
+//! literals and rejects unknown names at build time:
+//!
+//! ```rust
+//! use prettychars::{pretty, render};
+//!
+//! let line = render("{bold:CPU} {glyph:arrow.right} {mono:73%}").unwrap();
+//! let ok = pretty!("{circled:OK} {glyph:check.mark}");
+//! ```
+//!
+//! ## Markdown
+//!
+//! [`markdown_to_unicode`] converts Markdown for platforms that show it raw:
+//! emphasis, code and strikethrough become styles, headings get box-drawing
+//! rules, and list bullets and task items become glyphs. [`MarkdownStyles`]
+//! changes the mapping. [`html_to_unicode`] does the same for simple HTML
+//! (`<b>`, `<i>`, `<code>`, `<sup>`, ...), reporting the tags it strips.
+//!
+//! ```rust
+//! use prettychars::markdown_to_unicode;
+//!
+//! let notes = markdown_to_unicode("## Fixes\n- **Faster** `build`");
+//! ```
+//!
+//! ## Custom Styles
+//!
+//! [`style`] accepts any [`CharMap`]. Define your own with `const` tables or the
+//! [`char_map!`] macro; they share the built-in styles' zero-allocation path:
+//!
+//! ```rust
+//! use prettychars::{char_map, style};
+//!
+//! char_map! {
+//!     pub struct Leet {
+//!         'a' => '4',
+//!         'e' => '3',
+//!         'o' => '0',
+//!     }
+//! }
+//!
+//! assert_eq!(style("leet code", Leet), "l33t c0d3");
+//! ```
+//!
+//! ## Style Composition
+//!
+//! [`StyleSpec`] combines a [`Family`], weight, slant and combining [`Effect`]s,
+//! resolving to the matching Mathematical Alphanumeric style:
+//!
+//! ```rust
+//! use prettychars::{Effect, Family, StyleSpec};
+//!
+//! let spec = StyleSpec::new(Family::SansSerif)
+//!     .bold()
+//!     .italic()
+//!     .effect(Effect::Underline);
+//! let text = spec.apply("Rust").unwrap();          // 𝙍̲𝙪̲𝙨̲𝙩̲
+//! ```
+//!
+//! ## Text Diffs
+//!
+//! [`diff_to_unicode`] shows the changes between two strings as plain text,
+//! striking out deletions and underlining insertions with combining marks, so
+//! they survive anywhere ANSI colors do not. [`diff_to_unicode_with`] compares
+//! grapheme clusters instead of words:
+//!
+//! ```rust
+//! use prettychars::diff_to_unicode;
+//!
+//! let diff = diff_to_unicode("old plan", "new plan");   // o̶l̶d̶n̲e̲w̲ plan
+//! ```
 //!
 //! ## Named Glyphs
 //!
@@ -47,13 +153,71 @@
 //!
 //! Use [`glyph_names()`] to discover all available glyphs.
 //!
+//! [`banner`] draws FIGlet-style large letters from the block, quadrant and
+//! braille glyphs, with an ASCII fallback [`BannerFont`].
+//! [`BrailleCanvas`] draws lines, circles, rectangles and polygons at 2×4
+//! pixels per character through the [`Canvas`] trait, and [`BlockCanvas`] draws
+//! with half blocks, quadrants or sextants, falling back to what a
+//! [`RenderProfile`] supports. [`ImageRender`] draws PGM and PPM [`Image`]s with
+//! shades, blocks or braille, and [`BarChart`] and [`Histogram`] draw bars to an
+//! eighth of a cell.
+//!
 //! ## VS15 Handling
 //!
 //! All glyphs have VS15 (U+FE0E) variation selector applied automatically for
 //! consistent text rendering across platforms. This is transparent to callers.
 
+mod accessible;
+mod banner;
+mod canvas;
+mod cells;
+mod charmap;
+mod chart;
+mod compose;
+mod decompose;
+mod diff;
+mod flags;
 mod glyphs;
+mod grapheme;
+mod highlight;
+mod html;
+mod image;
+mod markdown;
+mod markup;
+mod metrics;
+mod options;
+mod protect;
 mod styles;
+mod writer;
+mod zalgo;
 
+pub use accessible::{
+    lint_accessibility, unstyle, A11yIssue, A11yIssueKind, A11yReport, Accessible,
+};
+pub use banner::{banner, Banner, BannerFont};
+pub use canvas::{BlockCanvas, BlockMode, BrailleCanvas, Canvas, RenderProfile};
+pub use charmap::{CharMap, Mapped};
+pub use chart::{BarChart, Histogram, Orientation};
+pub use compose::{Effect, Family, StyleSpec, UnsupportedStyle};
+pub use diff::{diff_to_unicode, diff_to_unicode_with, DiffGranularity};
+pub use flags::{country_code_of, flag, InvalidCountryCode};
 pub use glyphs::{glyph, glyph_names, UnknownGlyph};
-pub use styles::{style, style_names, Style};
+pub use grapheme::{graphemes, Graphemes};
+pub use highlight::{style_by, style_range, style_where};
+pub use html::{html_to_unicode, HtmlText, StrippedTag};
+pub use image::{Dither, Image, ImageError, ImageErrorKind, ImageMode, ImageRender};
+pub use markdown::{markdown_to_unicode, markdown_to_unicode_with, MarkdownStyles};
+#[doc(hidden)]
+pub use markup::__assert_markup;
+pub use markup::{render, MarkupError, MarkupErrorKind};
+pub use metrics::{truncate_to, Limit, TextMetrics};
+pub use options::{style_with, Fallback, StyleOptions};
+pub use protect::{
+    find_protected, style_text_smart, Protect, ProtectedSpan, SmartStyled, SpanKind,
+};
+pub use styles::{
+    style, style_names, try_style, AccentPolicy, Separator, Style, StyleError, SupportRisk,
+    UnknownStyle,
+};
+pub use writer::StyleWriter;
+pub use zalgo::{dezalgo, zalgo, ZalgoIntensity};
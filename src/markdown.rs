@@ -0,0 +1,550 @@
+use crate::glyphs::glyph;
+use crate::grapheme::graphemes;
+use crate::protect::scan_code;
+use crate::styles::{style, Style};
+
+/// Styles and glyphs used by [`markdown_to_unicode_with`]
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{markdown_to_unicode_with, MarkdownStyles, Style};
+///
+/// let styles = MarkdownStyles {
+///     bold: Style::SansSerifBold,
+///     ..MarkdownStyles::default()
+/// };
+/// assert_eq!(markdown_to_unicode_with("**Rust**", &styles), "𝗥𝘂𝘀𝘁");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MarkdownStyles {
+    /// `**strong**` and `__strong__`
+    pub bold: Style,
+    /// `*emphasis*` and `_emphasis_`
+    pub italic: Style,
+    /// `***both***`, and emphasis nested inside strong text or headings
+    pub bold_italic: Style,
+    /// `` `code` `` spans and fenced code blocks
+    pub code: Style,
+    /// `~~strikethrough~~`, applied on top of any other style
+    pub strike: Style,
+    /// Heading text
+    pub heading: Style,
+    /// Rule drawn under headings, indexed by level (`#` is index 0)
+    ///
+    /// Levels past the end of the list get no rule.
+    pub heading_rules: Vec<&'static str>,
+    /// Rule repeated for `---` thematic breaks
+    pub break_rule: &'static str,
+    /// Number of rule glyphs in a thematic break
+    pub break_width: usize,
+    /// Marker replacing `-`, `*` and `+` list bullets
+    pub bullet: &'static str,
+    /// Marker for `- [ ]` task items
+    pub task_open: &'static str,
+    /// Marker for `- [x]` task items
+    pub task_done: &'static str,
+}
+
+impl Default for MarkdownStyles {
+    fn default() -> Self {
+        let glyph = |name| glyph(name).expect("built-in glyph");
+        MarkdownStyles {
+            bold: Style::MathBold,
+            italic: Style::Italic,
+            bold_italic: Style::BoldItalic,
+            code: Style::Monospace,
+            strike: Style::Strikethrough,
+            heading: Style::SansSerifBold,
+            heading_rules: vec![glyph("box.heavy.h"), glyph("box.light.h")],
+            break_rule: glyph("box.light.h"),
+            break_width: 20,
+            bullet: glyph("bullet"),
+            task_open: glyph("check.empty"),
+            task_done: glyph("check.yes"),
+        }
+    }
+}
+
+/// Convert Markdown to styled plain text with the default [`MarkdownStyles`]
+///
+/// Emphasis, strong text, code and strikethrough become Unicode styles;
+/// headings become bold text over a box-drawing rule; list bullets and task
+/// items become glyphs. Links keep their URL in parentheses, and anything
+/// that is not Markdown syntax passes through unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::markdown_to_unicode;
+///
+/// assert_eq!(
+///     markdown_to_unicode("- [x] **Fast** `cargo` builds"),
+///     "☑︎ 𝐅𝐚𝐬𝐭 𝚌𝚊𝚛𝚐𝚘 builds"
+/// );
+/// ```
+pub fn markdown_to_unicode(md: &str) -> String {
+    markdown_to_unicode_with(md, &MarkdownStyles::default())
+}
+
+/// Convert Markdown to styled plain text with a custom style mapping
+///
+/// See [`markdown_to_unicode`].
+pub fn markdown_to_unicode_with(md: &str, styles: &MarkdownStyles) -> String {
+    let mut out = String::with_capacity(md.len() * 4);
+    let mut fence: Option<&str> = None;
+    for line in md.split_inclusive('\n') {
+        let body = line.trim_end_matches(['\n', '\r']);
+        let ending = &line[body.len()..];
+        let indent = body.len() - body.trim_start_matches(' ').len();
+        let content = &body[indent..];
+
+        if let Some(marker) = fence {
+            if content.starts_with(marker) && indent < 4 {
+                fence = None;
+            } else {
+                out.push_str(&style(body, styles.code));
+                out.push_str(ending);
+            }
+            continue;
+        }
+        if indent < 4 {
+            if let Some(marker) = fence_marker(content) {
+                fence = Some(marker);
+                continue;
+            }
+        }
+
+        let block = if indent < 4 {
+            Block::parse(content)
+        } else {
+            None
+        };
+        match block {
+            Some(Block::Heading(level, text)) => {
+                let mut heading = String::new();
+                render_inline(text, Emphasis::heading(), styles, &mut heading);
+                out.push_str(&heading);
+                if let Some(rule) = styles.heading_rules.get(level - 1) {
+                    out.push('\n');
+                    out.push_str(&rule.repeat(graphemes(&heading).count()));
+                }
+            }
+            Some(Block::Break) => out.push_str(&styles.break_rule.repeat(styles.break_width)),
+            Some(Block::Item(task, text)) => {
+                out.push_str(&body[..indent]);
+                out.push_str(match task {
+                    None => styles.bullet,
+                    Some(false) => styles.task_open,
+                    Some(true) => styles.task_done,
+                });
+                out.push(' ');
+                render_inline(text, Emphasis::default(), styles, &mut out);
+            }
+            None => render_inline(body, Emphasis::default(), styles, &mut out),
+        }
+        out.push_str(ending);
+    }
+    out
+}
+
+/// A line-level construct
+enum Block<'a> {
+    /// ATX heading level (1–6) and text
+    Heading(usize, &'a str),
+    /// `---`, `***` or `___`
+    Break,
+    /// Bullet item: `Some(checked)` for task items, then the item text
+    Item(Option<bool>, &'a str),
+}
+
+impl<'a> Block<'a> {
+    /// Parse a line with its indentation already removed
+    fn parse(line: &'a str) -> Option<Block<'a>> {
+        let hashes = line.len() - line.trim_start_matches('#').len();
+        if (1..=6).contains(&hashes) {
+            let rest = &line[hashes..];
+            if rest.is_empty() || rest.starts_with([' ', '\t']) {
+                return Some(Block::Heading(hashes, strip_closing_hashes(rest.trim())));
+            }
+        }
+
+        let marks: String = line.chars().filter(|c| !matches!(c, ' ' | '\t')).collect();
+        if marks.len() >= 3
+            && ["-", "*", "_"]
+                .iter()
+                .any(|m| marks.trim_start_matches(m).is_empty())
+        {
+            return Some(Block::Break);
+        }
+
+        let rest = line.strip_prefix(['-', '*', '+'])?;
+        let text = rest.strip_prefix([' ', '\t'])?.trim_start();
+        let task = match text.get(..4) {
+            Some("[ ] ") => Some(false),
+            Some("[x] " | "[X] ") => Some(true),
+            _ => None,
+        };
+        let text = if task.is_some() { &text[4..] } else { text };
+        Some(Block::Item(task, text))
+    }
+}
+
+/// Drop an optional closing `###` sequence from heading text
+fn strip_closing_hashes(text: &str) -> &str {
+    let stripped = text.trim_end_matches('#');
+    if stripped.is_empty() {
+        stripped
+    } else if stripped.len() < text.len() && stripped.ends_with([' ', '\t']) {
+        stripped.trim_end()
+    } else {
+        text
+    }
+}
+
+/// The ```` ``` ```` or `~~~` run opening a fenced code block
+fn fence_marker(line: &str) -> Option<&str> {
+    ["`", "~"].into_iter().find_map(|c| {
+        let len = line.len() - line.trim_start_matches(c).len();
+        let info = &line[len..];
+        // Backtick fences may not have backticks in their info string
+        (len >= 3 && !(c == "`" && info.contains('`'))).then_some(&line[..len])
+    })
+}
+
+/// Inline formatting in effect at a point in the text
+#[derive(Debug, Clone, Copy, Default)]
+struct Emphasis {
+    heading: bool,
+    bold: bool,
+    italic: bool,
+    strike: bool,
+}
+
+impl Emphasis {
+    fn heading() -> Self {
+        Emphasis {
+            heading: true,
+            ..Emphasis::default()
+        }
+    }
+
+    fn text_style(self, styles: &MarkdownStyles) -> Option<Style> {
+        match (self.bold || self.heading, self.italic) {
+            (true, true) => Some(styles.bold_italic),
+            (true, false) if self.bold => Some(styles.bold),
+            (true, false) => Some(styles.heading),
+            (false, true) => Some(styles.italic),
+            (false, false) => None,
+        }
+    }
+}
+
+/// Render inline Markdown, appending to `out`
+fn render_inline(text: &str, em: Emphasis, styles: &MarkdownStyles, out: &mut String) {
+    let mut plain = String::new();
+    let mut pos = 0;
+    while let Some(c) = text[pos..].chars().next() {
+        if let Some(end) = render_construct(text, pos, em, styles, &mut plain, out) {
+            pos = end;
+            continue;
+        }
+        // Unmatched delimiter runs are literal text
+        let len = if matches!(c, '`' | '*' | '_' | '~') {
+            run_len(text, pos, c)
+        } else {
+            c.len_utf8()
+        };
+        plain.push_str(&text[pos..pos + len]);
+        pos += len;
+    }
+    flush(&mut plain, em, styles, out);
+}
+
+/// Render the construct starting at `pos`, returning where it ends
+///
+/// Returns `None`, writing nothing, if no construct starts there.
+fn render_construct(
+    text: &str,
+    pos: usize,
+    em: Emphasis,
+    styles: &MarkdownStyles,
+    plain: &mut String,
+    out: &mut String,
+) -> Option<usize> {
+    let rest = &text[pos..];
+    match rest.chars().next()? {
+        '\\' => {
+            let escaped = rest[1..]
+                .chars()
+                .next()
+                .filter(char::is_ascii_punctuation)?;
+            plain.push(escaped);
+            Some(pos + 2)
+        }
+        '`' => {
+            let end = scan_code(text, pos)?;
+            let ticks = run_len(text, pos, '`');
+            let mut code = &text[pos + ticks..end - ticks];
+            if code.len() >= 2
+                && code.starts_with(' ')
+                && code.ends_with(' ')
+                && !code.trim().is_empty()
+            {
+                code = &code[1..code.len() - 1];
+            }
+            flush(plain, em, styles, out);
+            let styled = style(code, styles.code);
+            if em.strike {
+                out.push_str(&style(&styled, styles.strike));
+            } else {
+                out.push_str(&styled);
+            }
+            Some(end)
+        }
+        '!' if rest[1..].starts_with('[') => {
+            let (alt, _, end) = scan_link(text, pos + 1)?;
+            flush(plain, em, styles, out);
+            render_inline(alt, em, styles, out);
+            Some(end)
+        }
+        '[' => {
+            let (label, url, end) = scan_link(text, pos)?;
+            flush(plain, em, styles, out);
+            render_inline(label, em, styles, out);
+            if url != label && !url.is_empty() {
+                out.push_str(" (");
+                out.push_str(url);
+                out.push(')');
+            }
+            Some(end)
+        }
+        '<' => {
+            let end = scan_autolink(text, pos)?;
+            flush(plain, em, styles, out);
+            out.push_str(&text[pos + 1..end - 1]);
+            Some(end)
+        }
+        marker @ ('*' | '_' | '~') => {
+            let (run, close) = scan_emphasis(text, pos, marker)?;
+            let mut inner = em;
+            match (marker, run) {
+                ('~', _) => inner.strike = true,
+                (_, 1) => inner.italic = true,
+                (_, 2) => inner.bold = true,
+                _ => {
+                    inner.bold = true;
+                    inner.italic = true;
+                }
+            }
+            flush(plain, em, styles, out);
+            render_inline(&text[pos + run..close], inner, styles, out);
+            Some(close + run)
+        }
+        _ => None,
+    }
+}
+
+/// Style and append pending plain text
+fn flush(plain: &mut String, em: Emphasis, styles: &MarkdownStyles, out: &mut String) {
+    if plain.is_empty() {
+        return;
+    }
+    let styled = match em.text_style(styles) {
+        Some(text_style) => style(plain, text_style),
+        None => plain.clone(),
+    };
+    if em.strike {
+        out.push_str(&style(&styled, styles.strike));
+    } else {
+        out.push_str(&styled);
+    }
+    plain.clear();
+}
+
+fn run_len(text: &str, pos: usize, c: char) -> usize {
+    let rest = &text[pos..];
+    rest.len() - rest.trim_start_matches(c).len()
+}
+
+/// Match an emphasis delimiter run at `pos` with its closing run
+///
+/// Returns the run length and the byte offset of the closing run. Runs must
+/// have the same length; `~` runs must be exactly two long. Nested openers
+/// of the same length are balanced, and `_` never opens or closes inside a
+/// word, so `snake_case_names` stay literal.
+fn scan_emphasis(text: &str, pos: usize, marker: char) -> Option<(usize, usize)> {
+    let run = run_len(text, pos, marker);
+    let valid = if marker == '~' { run == 2 } else { run <= 3 };
+    let before = text[..pos].chars().next_back();
+    let after = text[pos + run..].chars().next();
+    let intraword = |c: Option<char>| marker == '_' && c.is_some_and(char::is_alphanumeric);
+    if !valid || !after.is_some_and(|c| !c.is_whitespace()) || intraword(before) {
+        return None;
+    }
+
+    let mut depth = 0;
+    let mut i = pos + run;
+    while let Some(c) = text[i..].chars().next() {
+        if c == '\\' {
+            i += 1 + text[i + 1..].chars().next().map_or(0, char::len_utf8);
+        } else if c == '`' {
+            i = scan_code(text, i).unwrap_or(i + run_len(text, i, '`'));
+        } else if c == marker {
+            let len = run_len(text, i, marker);
+            let prev = text[..i].chars().next_back();
+            let next = text[i + len..].chars().next();
+            if len == run {
+                let can_close = prev.is_some_and(|c| !c.is_whitespace()) && !intraword(next);
+                let can_open = prev.map_or(true, char::is_whitespace)
+                    && next.is_some_and(|c| !c.is_whitespace());
+                if can_close && depth == 0 {
+                    return Some((run, i));
+                } else if can_close {
+                    depth -= 1;
+                } else if can_open {
+                    depth += 1;
+                }
+            }
+            i += len;
+        } else {
+            i += c.len_utf8();
+        }
+    }
+    None
+}
+
+/// Match `[label](url "title")` at `pos`, returning the label, URL and end
+fn scan_link(text: &str, pos: usize) -> Option<(&str, &str, usize)> {
+    let label_end = scan_balanced(text, pos, '[', ']')?;
+    if !text[label_end..].starts_with('(') {
+        return None;
+    }
+    let end = scan_balanced(text, label_end, '(', ')')?;
+    let destination = text[label_end + 1..end - 1].trim();
+    let url = destination.split_whitespace().next().unwrap_or("");
+    let url = url
+        .strip_prefix('<')
+        .and_then(|url| url.strip_suffix('>'))
+        .unwrap_or(url);
+    Some((&text[pos + 1..label_end - 1], url, end))
+}
+
+/// Find the end of a bracketed group, skipping escapes and code spans
+fn scan_balanced(text: &str, pos: usize, open: char, close: char) -> Option<usize> {
+    let mut depth = 0;
+    let mut i = pos;
+    while let Some(c) = text[i..].chars().next() {
+        match c {
+            '\\' => i += 1 + text[i + 1..].chars().next().map_or(0, char::len_utf8),
+            '`' => i = scan_code(text, i).unwrap_or(i + run_len(text, i, '`')),
+            _ => {
+                if c == open {
+                    depth += 1;
+                } else if c == close {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(i + 1);
+                    }
+                }
+                i += c.len_utf8();
+            }
+        }
+    }
+    None
+}
+
+/// Match `<scheme:...>` or `<user@example.com>` at `pos`
+fn scan_autolink(text: &str, pos: usize) -> Option<usize> {
+    let len = text[pos + 1..].find(|c: char| c == '>' || c == '<' || c.is_whitespace())?;
+    let inner = &text[pos + 1..pos + 1 + len];
+    let is_link = inner.contains(':') || inner.contains('@');
+    (text[pos + 1 + len..].starts_with('>') && is_link).then_some(pos + len + 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn md(text: &str) -> String {
+        markdown_to_unicode(text)
+    }
+
+    #[test]
+    fn test_plain_text_round_trips() {
+        for text in [
+            "Nothing to see here.",
+            "2 * 3 * 4 = 24",
+            "snake_case_name stays put",
+            "a ~ b ~~ c",
+            "[not a link] (really)",
+            "1. ordered items keep their numbers\n> quotes too\n",
+            "trailing newline\r\n",
+        ] {
+            assert_eq!(md(text), text);
+        }
+    }
+
+    #[test]
+    fn test_inline_constructs() {
+        let cases = [
+            ("*em* _em_", "𝑒𝑚 𝑒𝑚"),
+            ("**st** __st__", "𝐬𝐭 𝐬𝐭"),
+            ("***both***", "𝒃𝒐𝒕𝒉"),
+            ("**a *b* c**", "𝐚 𝒃 𝐜"),
+            ("*a **b** c*", "𝑎 𝒃 𝑐"),
+            ("*a *b* c*", "𝑎 𝑏 𝑐"),
+            ("`x`", "𝚡"),
+            ("`` a`b ``", "𝚊`𝚋"),
+            ("`*not em*`", "*𝚗𝚘𝚝 𝚎𝚖*"),
+            ("~~no~~", "n\u{0336}o\u{0336}"),
+            ("~~**b**~~", "𝐛\u{0336}"),
+            ("\\*lit\\* \\_x\\_", "*lit* _x_"),
+            ("**open", "**open"),
+            ("* not em *", "•︎ not em *"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(md(input), expected, "input: {input}");
+        }
+    }
+
+    #[test]
+    fn test_links() {
+        assert_eq!(
+            md("[**docs**](https://x.io \"Docs\")"),
+            "𝐝𝐨𝐜𝐬 (https://x.io)"
+        );
+        assert_eq!(md("[https://x.io](https://x.io)"), "https://x.io");
+        assert_eq!(md("see <https://x.io/a_b_c>"), "see https://x.io/a_b_c");
+        assert_eq!(md("![alt *text*](a.png)"), "alt 𝑡𝑒𝑥𝑡");
+        assert_eq!(md("a <b> c"), "a <b> c");
+    }
+
+    #[test]
+    fn test_blocks() {
+        let input = "# Title #\n### Small\n\n- one\n  * [ ] two\n+ [x] three\n***\n";
+        let expected = "𝗧𝗶𝘁𝗹𝗲\n━︎━︎━︎━︎━︎\n𝗦𝗺𝗮𝗹𝗹\n\n•︎ one\n  ☐︎ two\n☑︎ three\n";
+        let rule = "─︎".repeat(20);
+        assert_eq!(md(input), format!("{expected}{rule}\n"));
+        assert_eq!(md("#hashtag"), "#hashtag");
+    }
+
+    #[test]
+    fn test_fenced_code() {
+        let input = "```rust\nlet *x* = 1;\n```\nafter";
+        assert_eq!(md(input), "𝚕𝚎𝚝 *𝚡* = 𝟷;\nafter");
+    }
+
+    #[test]
+    fn test_custom_styles() {
+        let styles = MarkdownStyles {
+            bold: Style::SansSerifBold,
+            heading: Style::MathBold,
+            heading_rules: vec![glyph("box.double.h").unwrap()],
+            bullet: "-",
+            ..MarkdownStyles::default()
+        };
+        let out = markdown_to_unicode_with("# Hi\n## Yo\n* **a**", &styles);
+        assert_eq!(out, "𝐇𝐢\n═︎═︎\n𝐘𝐨\n- 𝗮");
+    }
+}
@@ -0,0 +1,414 @@
+use crate::glyphs::{glyph, GLYPH_NAMES};
+use crate::styles::{style, Style};
+use std::fmt;
+
+/// Short aliases accepted in markup alongside the kebab-case [`Style::name`]s
+const STYLE_ALIASES: &[(&str, Style)] = &[
+    ("bold", Style::MathBold),
+    ("mono", Style::Monospace),
+    ("sans", Style::SansSerif),
+    ("sans-bold", Style::SansSerifBold),
+    ("strike", Style::Strikethrough),
+    ("sup", Style::Superscript),
+    ("sub", Style::Subscript),
+    ("wide", Style::Fullwidth),
+];
+
+/// Deepest allowed nesting of style spans
+const MAX_DEPTH: usize = 16;
+
+/// What went wrong while parsing a markup template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarkupErrorKind {
+    /// `{name:...}` names no style or alias
+    UnknownStyle(String),
+    /// `{glyph:name}` names no glyph
+    UnknownGlyph(String),
+    /// `{` is not followed by a name
+    EmptyName,
+    /// The span name is not followed by `:`
+    MissingColon,
+    /// A `{` has no matching `}`
+    UnclosedBrace,
+    /// A `}` has no matching `{`
+    UnmatchedBrace,
+    /// Spans are nested more than 16 deep
+    TooDeep,
+}
+
+/// Error returned by [`render`] for an invalid template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkupError {
+    /// What was wrong with the template
+    pub kind: MarkupErrorKind,
+    /// Byte offset in the template where the problem was found
+    pub offset: usize,
+}
+
+impl fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            MarkupErrorKind::UnknownStyle(name) => write!(f, "Unknown style '{}'", name)?,
+            MarkupErrorKind::UnknownGlyph(name) => write!(f, "Unknown glyph '{}'", name)?,
+            MarkupErrorKind::EmptyName => f.write_str("Expected a style name after '{'")?,
+            MarkupErrorKind::MissingColon => f.write_str("Expected ':' after style name")?,
+            MarkupErrorKind::UnclosedBrace => f.write_str("Unclosed '{'")?,
+            MarkupErrorKind::UnmatchedBrace => f.write_str("Unmatched '}'")?,
+            MarkupErrorKind::TooDeep => f.write_str("Spans nested too deeply")?,
+        }
+        write!(f, " at byte {}", self.offset)
+    }
+}
+
+impl std::error::Error for MarkupError {}
+
+/// A template problem found by [`check`], usable in `const` contexts
+#[derive(Debug, Clone, Copy)]
+enum Problem {
+    UnknownStyle,
+    UnknownGlyph,
+    EmptyName,
+    MissingColon,
+    UnclosedBrace,
+    UnmatchedBrace,
+    TooDeep,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Fault {
+    problem: Problem,
+    offset: usize,
+    /// End of the offending name, for unknown names
+    end: usize,
+}
+
+const fn fault(problem: Problem, offset: usize) -> Fault {
+    Fault {
+        problem,
+        offset,
+        end: offset,
+    }
+}
+
+impl Fault {
+    fn into_error(self, template: &str) -> MarkupError {
+        let name = template[self.offset..self.end].to_string();
+        let kind = match self.problem {
+            Problem::UnknownStyle => MarkupErrorKind::UnknownStyle(name),
+            Problem::UnknownGlyph => MarkupErrorKind::UnknownGlyph(name),
+            Problem::EmptyName => MarkupErrorKind::EmptyName,
+            Problem::MissingColon => MarkupErrorKind::MissingColon,
+            Problem::UnclosedBrace => MarkupErrorKind::UnclosedBrace,
+            Problem::UnmatchedBrace => MarkupErrorKind::UnmatchedBrace,
+            Problem::TooDeep => MarkupErrorKind::TooDeep,
+        };
+        MarkupError {
+            kind,
+            offset: self.offset,
+        }
+    }
+}
+
+const fn is_name_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_')
+}
+
+const fn is_escapable(b: u8) -> bool {
+    matches!(b, b'{' | b'}' | b'\\')
+}
+
+/// Compare `bytes[start..end]` with `name`
+const fn name_eq(bytes: &[u8], start: usize, end: usize, name: &str) -> bool {
+    let name = name.as_bytes();
+    if end - start != name.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < name.len() {
+        if bytes[start + i] != name[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn is_style_name(bytes: &[u8], start: usize, end: usize) -> bool {
+    let mut i = 0;
+    while i < STYLE_ALIASES.len() {
+        if name_eq(bytes, start, end, STYLE_ALIASES[i].0) {
+            return true;
+        }
+        i += 1;
+    }
+    let mut i = 0;
+    while i < Style::ALL.len() {
+        if name_eq(bytes, start, end, Style::ALL[i].name()) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+const fn is_glyph_name(bytes: &[u8], start: usize, end: usize) -> bool {
+    let mut i = 0;
+    while i < GLYPH_NAMES.len() {
+        if name_eq(bytes, start, end, GLYPH_NAMES[i]) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Validate a template without rendering it
+///
+/// Written as a `const fn` so [`pretty!`](crate::pretty) can run it at build time.
+const fn check(template: &str) -> Result<(), Fault> {
+    let bytes = template.as_bytes();
+    let mut open = [0usize; MAX_DEPTH];
+    let mut depth = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if i + 1 < bytes.len() && is_escapable(bytes[i + 1]) => i += 2,
+            b'{' => {
+                let start = i + 1;
+                let mut colon = start;
+                while colon < bytes.len() && is_name_byte(bytes[colon]) {
+                    colon += 1;
+                }
+                if colon == start {
+                    return Err(fault(Problem::EmptyName, start));
+                }
+                if colon == bytes.len() || bytes[colon] != b':' {
+                    return Err(fault(Problem::MissingColon, colon));
+                }
+                if name_eq(bytes, start, colon, "glyph") {
+                    let name_start = colon + 1;
+                    let mut close = name_start;
+                    while close < bytes.len() && bytes[close] != b'}' {
+                        close += 1;
+                    }
+                    if close == bytes.len() {
+                        return Err(fault(Problem::UnclosedBrace, i));
+                    }
+                    if !is_glyph_name(bytes, name_start, close) {
+                        return Err(Fault {
+                            problem: Problem::UnknownGlyph,
+                            offset: name_start,
+                            end: close,
+                        });
+                    }
+                    i = close + 1;
+                } else {
+                    if !is_style_name(bytes, start, colon) {
+                        return Err(Fault {
+                            problem: Problem::UnknownStyle,
+                            offset: start,
+                            end: colon,
+                        });
+                    }
+                    if depth == MAX_DEPTH {
+                        return Err(fault(Problem::TooDeep, i));
+                    }
+                    open[depth] = i;
+                    depth += 1;
+                    i = colon + 1;
+                }
+            }
+            b'}' => {
+                if depth == 0 {
+                    return Err(fault(Problem::UnmatchedBrace, i));
+                }
+                depth -= 1;
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+    if depth > 0 {
+        return Err(fault(Problem::UnclosedBrace, open[depth - 1]));
+    }
+    Ok(())
+}
+
+/// Panic at build time if a template is invalid; used by [`pretty!`](crate::pretty)
+#[doc(hidden)]
+pub const fn __assert_markup(template: &str) {
+    if let Err(fault) = check(template) {
+        let message = match fault.problem {
+            Problem::UnknownStyle => "markup template names an unknown style",
+            Problem::UnknownGlyph => "markup template names an unknown glyph",
+            Problem::EmptyName => "markup template has '{' without a style name",
+            Problem::MissingColon => "markup template has a style name without ':'",
+            Problem::UnclosedBrace => "markup template has an unclosed '{'",
+            Problem::UnmatchedBrace => "markup template has an unmatched '}'",
+            Problem::TooDeep => "markup template nests spans too deeply",
+        };
+        panic!("{}", message);
+    }
+}
+
+fn resolve_style(name: &str) -> Style {
+    STYLE_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == name)
+        .map(|&(_, style)| style)
+        .or_else(|| Style::ALL.iter().find(|s| s.name() == name).copied())
+        .expect("style names are validated before rendering")
+}
+
+/// Render a validated template from `pos` up to the `}` closing the current span
+fn render_span(template: &str, pos: &mut usize) -> String {
+    let bytes = template.as_bytes();
+    let mut out = String::new();
+    let mut text_start = *pos;
+    while *pos < bytes.len() {
+        match bytes[*pos] {
+            b'\\' if *pos + 1 < bytes.len() && is_escapable(bytes[*pos + 1]) => {
+                out.push_str(&template[text_start..*pos]);
+                out.push(bytes[*pos + 1] as char);
+                *pos += 2;
+                text_start = *pos;
+            }
+            b'{' => {
+                out.push_str(&template[text_start..*pos]);
+                let colon = *pos + template[*pos..].find(':').expect("validated template");
+                let name = &template[*pos + 1..colon];
+                *pos = colon + 1;
+                if name == "glyph" {
+                    let close = *pos + template[*pos..].find('}').expect("validated template");
+                    out.push_str(glyph(&template[*pos..close]).expect("validated template"));
+                    *pos = close + 1;
+                } else {
+                    let inner = render_span(template, pos);
+                    out.push_str(&style(&inner, resolve_style(name)));
+                    *pos += 1;
+                }
+                text_start = *pos;
+            }
+            b'}' => break,
+            _ => *pos += 1,
+        }
+    }
+    out.push_str(&template[text_start..*pos]);
+    out
+}
+
+/// Render an inline markup template of styled spans and glyphs
+///
+/// - `{style:text}` applies a style by its [`Style::name`] or a short alias
+///   (`bold`, `mono`, `sans`, `sans-bold`, `strike`, `sup`, `sub`, `wide`)
+/// - `{glyph:name}` inserts a named glyph
+/// - Spans nest; inner spans are styled first and keep their style
+/// - `\{`, `\}` and `\\` produce literal characters
+///
+/// Use [`pretty!`](crate::pretty) to validate a literal template at build time.
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::render;
+///
+/// let line = render("{bold:CPU} {glyph:arrow.right} {mono:73%} {circled:OK}").unwrap();
+/// assert_eq!(line, "𝐂𝐏𝐔 →\u{FE0E} 𝟽𝟹% ⓄⓀ");
+///
+/// let nested = render("{bold:a{italic:b}c} \\{x\\}").unwrap();
+/// assert_eq!(nested, "𝐚𝑏𝐜 {x}");
+/// ```
+///
+/// # Errors
+///
+/// Returns [`MarkupError`] with the byte offset of the first problem:
+///
+/// ```
+/// use prettychars::{render, MarkupErrorKind};
+///
+/// let err = render("{bold:ok} {glyph:nope}").unwrap_err();
+/// assert_eq!(err.kind, MarkupErrorKind::UnknownGlyph("nope".to_string()));
+/// assert_eq!(err.offset, 17);
+/// ```
+pub fn render(template: &str) -> Result<String, MarkupError> {
+    check(template).map_err(|fault| fault.into_error(template))?;
+    Ok(render_span(template, &mut 0))
+}
+
+/// Render a markup template literal, validating it at build time
+///
+/// Accepts the same syntax as [`render`]. Unknown style or glyph names and
+/// unbalanced braces are compile errors.
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::pretty;
+///
+/// let status = pretty!("{glyph:check.mark} {sans-bold:Ready}");
+/// assert_eq!(status, "✓\u{FE0E} 𝗥𝗲𝗮𝗱𝘆");
+/// ```
+///
+/// ```compile_fail
+/// let status = prettychars::pretty!("{glyph:check.maybe} Ready");
+/// ```
+#[macro_export]
+macro_rules! pretty {
+    ($template:expr) => {{
+        const TEMPLATE: &str = $template;
+        const CHECKED: () = $crate::__assert_markup(TEMPLATE);
+        #[allow(clippy::let_unit_value)]
+        let () = CHECKED;
+        $crate::render(TEMPLATE).expect("template validated at compile time")
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_style_name_addressable() {
+        for &s in Style::ALL {
+            let template = format!("{{{}:a}}", s.name());
+            assert_eq!(render(&template).unwrap(), style("a", s));
+        }
+    }
+
+    #[test]
+    fn test_positioned_errors() {
+        let err = |t: &str| render(t).unwrap_err();
+        assert_eq!(
+            err("x {boldd:y}").kind,
+            MarkupErrorKind::UnknownStyle("boldd".to_string())
+        );
+        assert_eq!(err("x {boldd:y}").offset, 3);
+        assert_eq!(err("{bold:y").offset, 0);
+        assert_eq!(err("{bold:{mono:y}").kind, MarkupErrorKind::UnclosedBrace);
+        assert_eq!(err("a}").offset, 1);
+        assert_eq!(err("{bold y}").kind, MarkupErrorKind::MissingColon);
+        assert_eq!(err("{:y}").kind, MarkupErrorKind::EmptyName);
+    }
+
+    #[test]
+    fn test_escapes_inside_spans() {
+        assert_eq!(render("{mono:\\}}").unwrap(), "}");
+        assert_eq!(render("a\\\\b \\n").unwrap(), "a\\b \\n");
+    }
+
+    #[test]
+    fn test_glyphs_inside_styles() {
+        assert_eq!(
+            render("{strike:a{glyph:star}}").unwrap(),
+            "a\u{0336}★\u{FE0E}\u{0336}"
+        );
+    }
+
+    #[test]
+    fn test_macro_matches_render() {
+        assert_eq!(
+            pretty!("{sup:2}{glyph:dot}"),
+            render("{sup:2}{glyph:dot}").unwrap()
+        );
+    }
+}
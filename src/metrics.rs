@@ -0,0 +1,353 @@
+use crate::grapheme::{graphemes, is_zero_width};
+
+/// East Asian Width Wide and Fullwidth ranges, sorted by start (Unicode 14.0)
+///
+/// Includes emoji with default emoji presentation, which are Wide.
+static WIDE: &[(char, char)] = &[
+    ('\u{1100}', '\u{115F}'),
+    ('\u{231A}', '\u{231B}'),
+    ('\u{2329}', '\u{232A}'),
+    ('\u{23E9}', '\u{23EC}'),
+    ('\u{23F0}', '\u{23F0}'),
+    ('\u{23F3}', '\u{23F3}'),
+    ('\u{25FD}', '\u{25FE}'),
+    ('\u{2614}', '\u{2615}'),
+    ('\u{2648}', '\u{2653}'),
+    ('\u{267F}', '\u{267F}'),
+    ('\u{2693}', '\u{2693}'),
+    ('\u{26A1}', '\u{26A1}'),
+    ('\u{26AA}', '\u{26AB}'),
+    ('\u{26BD}', '\u{26BE}'),
+    ('\u{26C4}', '\u{26C5}'),
+    ('\u{26CE}', '\u{26CE}'),
+    ('\u{26D4}', '\u{26D4}'),
+    ('\u{26EA}', '\u{26EA}'),
+    ('\u{26F2}', '\u{26F3}'),
+    ('\u{26F5}', '\u{26F5}'),
+    ('\u{26FA}', '\u{26FA}'),
+    ('\u{26FD}', '\u{26FD}'),
+    ('\u{2705}', '\u{2705}'),
+    ('\u{270A}', '\u{270B}'),
+    ('\u{2728}', '\u{2728}'),
+    ('\u{274C}', '\u{274C}'),
+    ('\u{274E}', '\u{274E}'),
+    ('\u{2753}', '\u{2755}'),
+    ('\u{2757}', '\u{2757}'),
+    ('\u{2795}', '\u{2797}'),
+    ('\u{27B0}', '\u{27B0}'),
+    ('\u{27BF}', '\u{27BF}'),
+    ('\u{2B1B}', '\u{2B1C}'),
+    ('\u{2B50}', '\u{2B50}'),
+    ('\u{2B55}', '\u{2B55}'),
+    ('\u{2E80}', '\u{2E99}'),
+    ('\u{2E9B}', '\u{2EF3}'),
+    ('\u{2F00}', '\u{2FD5}'),
+    ('\u{2FF0}', '\u{2FFB}'),
+    ('\u{3000}', '\u{303E}'),
+    ('\u{3041}', '\u{3096}'),
+    ('\u{3099}', '\u{30FF}'),
+    ('\u{3105}', '\u{312F}'),
+    ('\u{3131}', '\u{318E}'),
+    ('\u{3190}', '\u{31E3}'),
+    ('\u{31F0}', '\u{321E}'),
+    ('\u{3220}', '\u{3247}'),
+    ('\u{3250}', '\u{4DBF}'),
+    ('\u{4E00}', '\u{A48C}'),
+    ('\u{A490}', '\u{A4C6}'),
+    ('\u{A960}', '\u{A97C}'),
+    ('\u{AC00}', '\u{D7A3}'),
+    ('\u{F900}', '\u{FA6D}'),
+    ('\u{FA70}', '\u{FAD9}'),
+    ('\u{FE10}', '\u{FE19}'),
+    ('\u{FE30}', '\u{FE52}'),
+    ('\u{FE54}', '\u{FE66}'),
+    ('\u{FE68}', '\u{FE6B}'),
+    ('\u{FF01}', '\u{FF60}'),
+    ('\u{FFE0}', '\u{FFE6}'),
+    ('\u{16FE0}', '\u{16FE4}'),
+    ('\u{16FF0}', '\u{16FF1}'),
+    ('\u{17000}', '\u{187F7}'),
+    ('\u{18800}', '\u{18CD5}'),
+    ('\u{18D00}', '\u{18D08}'),
+    ('\u{1AFF0}', '\u{1AFF3}'),
+    ('\u{1AFF5}', '\u{1AFFB}'),
+    ('\u{1AFFD}', '\u{1AFFE}'),
+    ('\u{1B000}', '\u{1B122}'),
+    ('\u{1B150}', '\u{1B152}'),
+    ('\u{1B164}', '\u{1B167}'),
+    ('\u{1B170}', '\u{1B2FB}'),
+    ('\u{1F004}', '\u{1F004}'),
+    ('\u{1F0CF}', '\u{1F0CF}'),
+    ('\u{1F18E}', '\u{1F18E}'),
+    ('\u{1F191}', '\u{1F19A}'),
+    ('\u{1F200}', '\u{1F202}'),
+    ('\u{1F210}', '\u{1F23B}'),
+    ('\u{1F240}', '\u{1F248}'),
+    ('\u{1F250}', '\u{1F251}'),
+    ('\u{1F260}', '\u{1F265}'),
+    ('\u{1F300}', '\u{1F320}'),
+    ('\u{1F32D}', '\u{1F335}'),
+    ('\u{1F337}', '\u{1F37C}'),
+    ('\u{1F37E}', '\u{1F393}'),
+    ('\u{1F3A0}', '\u{1F3CA}'),
+    ('\u{1F3CF}', '\u{1F3D3}'),
+    ('\u{1F3E0}', '\u{1F3F0}'),
+    ('\u{1F3F4}', '\u{1F3F4}'),
+    ('\u{1F3F8}', '\u{1F43E}'),
+    ('\u{1F440}', '\u{1F440}'),
+    ('\u{1F442}', '\u{1F4FC}'),
+    ('\u{1F4FF}', '\u{1F53D}'),
+    ('\u{1F54B}', '\u{1F54E}'),
+    ('\u{1F550}', '\u{1F567}'),
+    ('\u{1F57A}', '\u{1F57A}'),
+    ('\u{1F595}', '\u{1F596}'),
+    ('\u{1F5A4}', '\u{1F5A4}'),
+    ('\u{1F5FB}', '\u{1F64F}'),
+    ('\u{1F680}', '\u{1F6C5}'),
+    ('\u{1F6CC}', '\u{1F6CC}'),
+    ('\u{1F6D0}', '\u{1F6D2}'),
+    ('\u{1F6D5}', '\u{1F6D7}'),
+    ('\u{1F6DD}', '\u{1F6DF}'),
+    ('\u{1F6EB}', '\u{1F6EC}'),
+    ('\u{1F6F4}', '\u{1F6FC}'),
+    ('\u{1F7E0}', '\u{1F7EB}'),
+    ('\u{1F7F0}', '\u{1F7F0}'),
+    ('\u{1F90C}', '\u{1F93A}'),
+    ('\u{1F93C}', '\u{1F945}'),
+    ('\u{1F947}', '\u{1F9FF}'),
+    ('\u{1FA70}', '\u{1FA74}'),
+    ('\u{1FA78}', '\u{1FA7C}'),
+    ('\u{1FA80}', '\u{1FA86}'),
+    ('\u{1FA90}', '\u{1FAAC}'),
+    ('\u{1FAB0}', '\u{1FABA}'),
+    ('\u{1FAC0}', '\u{1FAC5}'),
+    ('\u{1FAD0}', '\u{1FAD9}'),
+    ('\u{1FAE0}', '\u{1FAE7}'),
+    ('\u{1FAF0}', '\u{1FAF6}'),
+    ('\u{20000}', '\u{2FFFD}'),
+    ('\u{30000}', '\u{3FFFD}'),
+];
+
+fn is_wide(c: char) -> bool {
+    WIDE.binary_search_by(|&(start, end)| {
+        if end < c {
+            std::cmp::Ordering::Less
+        } else if start > c {
+            std::cmp::Ordering::Greater
+        } else {
+            std::cmp::Ordering::Equal
+        }
+    })
+    .is_ok()
+}
+
+/// Terminal columns taken by one grapheme cluster
+///
+/// Wide and fullwidth characters, regional indicators and emoji-presentation
+/// (VS16) sequences take two columns; VS15 never adds width.
+fn cluster_columns(cluster: &str) -> usize {
+    let Some(base) = cluster.chars().next() else {
+        return 0;
+    };
+    if base.is_ascii() && cluster.len() == 1 {
+        usize::from(!base.is_ascii_control())
+    } else if is_zero_width(base) {
+        0
+    } else if is_wide(base)
+        || ('\u{1F1E6}'..='\u{1F1FF}').contains(&base)
+        || cluster.contains('\u{FE0F}')
+    {
+        2
+    } else {
+        1
+    }
+}
+
+/// Lengths of a string in the units platforms count
+///
+/// Mathematical alphanumerics take 4 UTF-8 bytes and 2 UTF-16 code units each,
+/// so styled text can exceed a limit that the plain text fits.
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{style, Style, TextMetrics};
+///
+/// let metrics = TextMetrics::of(&style("Hi", Style::MathBold));
+/// assert_eq!(metrics.bytes, 8);
+/// assert_eq!(metrics.utf16_units, 4);
+/// assert_eq!(metrics.code_points, 2);
+/// assert_eq!(metrics.graphemes, 2);
+/// assert_eq!(metrics.columns, 2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct TextMetrics {
+    /// UTF-8 bytes
+    pub bytes: usize,
+    /// UTF-16 code units, as counted by JavaScript `length` and UCS-2 SMS
+    pub utf16_units: usize,
+    /// Unicode scalar values
+    pub code_points: usize,
+    /// Extended grapheme clusters, roughly what a reader sees as characters
+    pub graphemes: usize,
+    /// Monospace terminal columns
+    pub columns: usize,
+}
+
+impl TextMetrics {
+    /// Measure `text`
+    pub fn of(text: &str) -> Self {
+        let mut metrics = TextMetrics {
+            bytes: text.len(),
+            ..TextMetrics::default()
+        };
+        for c in text.chars() {
+            metrics.code_points += 1;
+            metrics.utf16_units += c.len_utf16();
+        }
+        for cluster in graphemes(text) {
+            metrics.graphemes += 1;
+            metrics.columns += cluster_columns(cluster);
+        }
+        metrics
+    }
+
+    /// Whether the measured text is within `limit`
+    pub fn fits(&self, limit: Limit) -> bool {
+        let used = match limit {
+            Limit::Bytes(_) => self.bytes,
+            Limit::Utf16(_) => self.utf16_units,
+            Limit::CodePoints(_) => self.code_points,
+            Limit::Graphemes(_) => self.graphemes,
+            Limit::Columns(_) => self.columns,
+        };
+        used <= limit.max()
+    }
+}
+
+/// A maximum length in one of the units of [`TextMetrics`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Limit {
+    /// UTF-8 bytes, for byte-limited fields and databases
+    Bytes(usize),
+    /// UTF-16 code units, for JavaScript-counted limits
+    Utf16(usize),
+    /// Unicode scalar values
+    CodePoints(usize),
+    /// Extended grapheme clusters, for limits counted as a reader would
+    Graphemes(usize),
+    /// Monospace terminal columns
+    Columns(usize),
+}
+
+impl Limit {
+    const fn max(self) -> usize {
+        match self {
+            Limit::Bytes(n)
+            | Limit::Utf16(n)
+            | Limit::CodePoints(n)
+            | Limit::Graphemes(n)
+            | Limit::Columns(n) => n,
+        }
+    }
+
+    /// Length of one grapheme cluster, or of a whole string, in this unit
+    fn measure(self, text: &str) -> usize {
+        match self {
+            Limit::Bytes(_) => text.len(),
+            Limit::Utf16(_) => text.encode_utf16().count(),
+            Limit::CodePoints(_) => text.chars().count(),
+            Limit::Graphemes(_) => graphemes(text).count(),
+            Limit::Columns(_) => graphemes(text).map(cluster_columns).sum(),
+        }
+    }
+}
+
+/// Shorten text to fit a limit, ending it with `ellipsis` if anything was cut
+///
+/// Text that already fits is returned unchanged. Otherwise whole grapheme
+/// clusters are kept, so surrogate pairs, combining overlays and VS15 glyph
+/// sequences are never split, trailing whitespace is trimmed, and
+/// `ellipsis` is appended. An ellipsis longer than the limit is dropped.
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{style, truncate_to, Limit, Style};
+///
+/// let bio = style("Rust and typography", Style::MathBold);
+/// let short = truncate_to(&bio, Limit::Utf16(18), "…");
+/// assert_eq!(short, "𝐑𝐮𝐬𝐭 𝐚𝐧𝐝…");
+/// ```
+pub fn truncate_to(text: &str, limit: Limit, ellipsis: &str) -> String {
+    let max = limit.max();
+    if limit.measure(text) <= max {
+        return text.to_string();
+    }
+    let (ellipsis, budget) = match max.checked_sub(limit.measure(ellipsis)) {
+        Some(budget) => (ellipsis, budget),
+        None => ("", max),
+    };
+    let mut used = 0;
+    let mut end = 0;
+    for cluster in graphemes(text) {
+        used += limit.measure(cluster);
+        if used > budget {
+            break;
+        }
+        end += cluster.len();
+    }
+    let kept = if ellipsis.is_empty() {
+        &text[..end]
+    } else {
+        text[..end].trim_end()
+    };
+    format!("{kept}{ellipsis}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::glyph;
+
+    #[test]
+    fn test_metrics() {
+        let metrics = TextMetrics::of("e\u{0301}日本👍🏽\t");
+        assert_eq!(metrics.code_points, 7);
+        assert_eq!(metrics.utf16_units, 9);
+        assert_eq!(metrics.graphemes, 5);
+        assert_eq!(metrics.columns, 7);
+        assert!(metrics.fits(Limit::Columns(7)));
+        assert!(!metrics.fits(Limit::Graphemes(4)));
+    }
+
+    #[test]
+    fn test_columns() {
+        let columns = |text| TextMetrics::of(text).columns;
+        assert_eq!(columns(glyph("star").unwrap()), 1);
+        assert_eq!(columns("❤\u{FE0F}"), 2);
+        assert_eq!(columns("🇺🇸"), 2);
+        assert_eq!(columns("ＡＢ"), 4);
+        assert_eq!(columns("\u{200B}\u{0301}"), 0);
+        assert_eq!(columns("한글"), 4);
+    }
+
+    #[test]
+    fn test_truncate_keeps_sequences_whole() {
+        // Each bold letter is a surrogate pair
+        assert_eq!(truncate_to("𝐚𝐛𝐜", Limit::Utf16(5), ""), "𝐚𝐛");
+        // Each struck letter is 3 bytes
+        assert_eq!(truncate_to("a̶b̶c̶", Limit::Bytes(8), ""), "a̶b̶");
+        let stars = glyph("star").unwrap().repeat(3);
+        let kept = truncate_to(&stars, Limit::CodePoints(5), "");
+        assert_eq!(kept, glyph("star").unwrap().repeat(2));
+    }
+
+    #[test]
+    fn test_truncate_ellipsis() {
+        assert_eq!(truncate_to("fits", Limit::Graphemes(4), "…"), "fits");
+        assert_eq!(truncate_to("one two", Limit::Graphemes(5), "…"), "one…");
+        assert_eq!(truncate_to("abc", Limit::Graphemes(2), "..."), "ab");
+        assert_eq!(truncate_to("日本語", Limit::Columns(5), "…"), "日本…");
+    }
+}
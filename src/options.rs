@@ -0,0 +1,243 @@
+use crate::charmap::CharMap;
+use crate::charmap::{push_cluster, push_cluster_parts, style_clusters};
+use crate::styles::{AccentPolicy, Separator, Style};
+
+/// A fallback step tried when a style has no mapping for a character
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Fallback {
+    /// Retry the primary style with the opposite case (`a` → `A` for `Squared`)
+    CaseFold,
+
+    /// Retry with an alternate style (e.g. `Squared` digits → `Circled`)
+    Style(Style),
+
+    /// Replace the character with a fixed substitute
+    Substitute(char),
+}
+
+/// Options controlling how [`style_with`] handles unmapped characters
+///
+/// Fallbacks are tried in order; the first one that produces output wins.
+/// Characters no fallback can handle are passed through unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{style_with, Fallback, Style, StyleOptions};
+///
+/// let options = StyleOptions::new()
+///     .fallback(Fallback::CaseFold)
+///     .fallback(Fallback::Style(Style::Circled));
+/// assert_eq!(style_with("Ok 1", Style::Squared, &options), "🄾🄺 ①");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct StyleOptions {
+    /// Steps tried in order for characters the style has no mapping for
+    pub fallbacks: Vec<Fallback>,
+    /// Override for [`Style::accent_policy`]
+    pub accents: Option<AccentPolicy>,
+    /// Override for the separator of styles that use one
+    /// ([`Style::RegionalIndicator`])
+    pub separator: Option<Separator>,
+}
+
+impl StyleOptions {
+    /// Options with no fallbacks and the style's default accent policy
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Recommended fallback chain for a style
+    ///
+    /// Single-case styles fold to their supported case, and `Squared` borrows
+    /// `Circled` digits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use prettychars::{style_with, Style, StyleOptions};
+    ///
+    /// let options = StyleOptions::recommended(Style::Parenthesized);
+    /// assert_eq!(style_with("Hi", Style::Parenthesized, &options), "⒣⒤");
+    /// ```
+    pub fn recommended(style: Style) -> Self {
+        let fallbacks = match style {
+            Style::Squared => vec![Fallback::CaseFold, Fallback::Style(Style::Circled)],
+            Style::SquaredNegative | Style::CircledNegative | Style::Parenthesized => {
+                vec![Fallback::CaseFold]
+            }
+            _ => Vec::new(),
+        };
+        StyleOptions {
+            fallbacks,
+            ..StyleOptions::default()
+        }
+    }
+
+    /// Append a fallback step to the chain
+    pub fn fallback(mut self, fallback: Fallback) -> Self {
+        self.fallbacks.push(fallback);
+        self
+    }
+
+    /// Override the accent policy for every style in the chain
+    pub fn accents(mut self, policy: AccentPolicy) -> Self {
+        self.accents = Some(policy);
+        self
+    }
+
+    /// Override the separator placed between regional indicator letters
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use prettychars::{style_with, Separator, Style, StyleOptions};
+    ///
+    /// let options = StyleOptions::new().separator(Separator::ZeroWidthSpace);
+    /// assert_eq!(
+    ///     style_with("UK", Style::RegionalIndicator, &options),
+    ///     "🇺\u{200B}🇰"
+    /// );
+    /// ```
+    pub fn separator(mut self, separator: Separator) -> Self {
+        self.separator = Some(separator);
+        self
+    }
+
+    fn policy(&self, style: Style) -> AccentPolicy {
+        self.accents.unwrap_or_else(|| style.accent_policy())
+    }
+
+    fn separator_for(&self, style: Style) -> Option<char> {
+        style
+            .separator()
+            .map(|sep| self.separator.map_or(sep, Separator::as_char))
+    }
+
+    /// Append a grapheme cluster converted through the fallback chain
+    ///
+    /// Called after the primary style failed. Returns the step that produced
+    /// output, or `None`, writing nothing, if no step does.
+    fn push_fallback(&self, cluster: &str, style: Style, out: &mut String) -> Option<Fallback> {
+        let mut chars = cluster.chars();
+        let base = chars.next()?;
+        let marks = chars.as_str();
+        self.fallbacks
+            .iter()
+            .copied()
+            .find(|fallback| match *fallback {
+                Fallback::CaseFold => fold_case(base).is_some_and(|folded| {
+                    push_cluster_parts(&style, folded, marks, self.policy(style), out)
+                }),
+                Fallback::Style(alt) => push_cluster(&alt, cluster, self.policy(alt), out),
+                Fallback::Substitute(sub) if !base.is_whitespace() && !base.is_control() => {
+                    out.push(sub);
+                    true
+                }
+                Fallback::Substitute(_) => false,
+            })
+    }
+}
+
+/// The opposite-case form of `c`, if it is a single character
+fn fold_case(c: char) -> Option<char> {
+    let mut folded: Vec<char> = if c.is_uppercase() {
+        c.to_lowercase().collect()
+    } else if c.is_lowercase() {
+        c.to_uppercase().collect()
+    } else {
+        return None;
+    };
+    if folded.len() == 1 {
+        folded.pop()
+    } else {
+        None
+    }
+}
+
+/// Convert text to a Unicode style, applying fallbacks for unmapped characters
+///
+/// Whitespace and control characters are never substituted; a substitute
+/// replaces a whole grapheme cluster.
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{style_with, Fallback, Style, StyleOptions};
+///
+/// let options = StyleOptions::new().fallback(Fallback::Substitute('·'));
+/// assert_eq!(style_with("a1 b", Style::SmallCaps, &options), "ᴀ· ʙ");
+/// ```
+pub fn style_with(text: &str, style: Style, options: &StyleOptions) -> String {
+    // End offset and separator of the last cluster a separated fallback styled
+    let mut joined: Option<(usize, char)> = None;
+    style_clusters(
+        text,
+        &style,
+        options.policy(style),
+        options.separator_for(style),
+        |offset, cluster, out| {
+            let start = out.len();
+            let separator = match options.push_fallback(cluster, style, out) {
+                Some(Fallback::Style(alt)) => options.separator_for(alt),
+                Some(_) => None,
+                None => {
+                    out.push_str(cluster);
+                    None
+                }
+            };
+            // Keep adjacent fallback clusters apart too, so letters don't fuse
+            if let Some(sep) = separator.filter(|&sep| joined == Some((offset, sep))) {
+                out.insert(start, sep);
+            }
+            joined = separator.map(|sep| (offset + cluster.len(), sep));
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_fallbacks_matches_style() {
+        let options = StyleOptions::new();
+        assert_eq!(style_with("Hi 1", Style::Squared, &options), "🄷i 1");
+    }
+
+    #[test]
+    fn test_case_fold() {
+        let options = StyleOptions::new().fallback(Fallback::CaseFold);
+        assert_eq!(style_with("ok", Style::SquaredNegative, &options), "🅾🅺");
+        assert_eq!(style_with("OK", Style::Parenthesized, &options), "⒪⒦");
+    }
+
+    #[test]
+    fn test_chain_order() {
+        let options = StyleOptions::new()
+            .fallback(Fallback::Style(Style::Circled))
+            .fallback(Fallback::CaseFold);
+        // Circled maps lowercase directly, so it wins over case folding
+        assert_eq!(style_with("a", Style::Squared, &options), "ⓐ");
+    }
+
+    #[test]
+    fn test_substitute_skips_whitespace() {
+        let options = StyleOptions::new().fallback(Fallback::Substitute('?'));
+        assert_eq!(style_with("a b\n", Style::Squared, &options), "? ?\n");
+    }
+
+    #[test]
+    fn test_fallback_separator() {
+        let options = StyleOptions::new().fallback(Fallback::Style(Style::RegionalIndicator));
+        assert_eq!(style_with("us", Style::Squared, &options), "🇺\u{200C}🇸");
+        // Clusters the primary style maps break the run
+        assert_eq!(style_with("uSs", Style::Squared, &options), "🇺🅂🇸");
+    }
+
+    #[test]
+    fn test_accent_override() {
+        let options = StyleOptions::new().accents(AccentPolicy::Preserve);
+        assert_eq!(style_with("é", Style::MathBold, &options), "é");
+    }
+}
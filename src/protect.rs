@@ -0,0 +1,338 @@
+use crate::charmap::{style_clusters, CharMap};
+use std::ops::{BitOr, BitOrAssign, Range};
+
+/// Set of span kinds that [`style_text_smart`] leaves unstyled
+///
+/// Combine kinds with `|`:
+///
+/// ```
+/// use prettychars::Protect;
+///
+/// let protect = Protect::URLS | Protect::MENTIONS;
+/// assert!(protect.contains(Protect::URLS));
+/// assert!(!protect.contains(Protect::HASHTAGS));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Protect(u8);
+
+impl Protect {
+    /// Protect nothing
+    pub const NONE: Protect = Protect(0);
+    /// `http://`, `https://`, `ftp://`, `mailto:` and `www.` links
+    pub const URLS: Protect = Protect(1);
+    /// `@handle` and `@handle@instance.example` mentions
+    pub const MENTIONS: Protect = Protect(1 << 1);
+    /// `#hashtag` tags
+    pub const HASHTAGS: Protect = Protect(1 << 2);
+    /// `` `code` `` spans, including their backticks
+    pub const BACKTICKS: Protect = Protect(1 << 3);
+    /// Every kind of span
+    pub const ALL: Protect = Protect(0b1111);
+
+    /// Whether every kind in `other` is also in `self`
+    pub const fn contains(self, other: Protect) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for Protect {
+    type Output = Protect;
+
+    fn bitor(self, rhs: Protect) -> Protect {
+        Protect(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Protect {
+    fn bitor_assign(&mut self, rhs: Protect) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Kind of a protected span
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpanKind {
+    /// A link, matched by [`Protect::URLS`]
+    Url,
+    /// An `@handle`, matched by [`Protect::MENTIONS`]
+    Mention,
+    /// A `#hashtag`, matched by [`Protect::HASHTAGS`]
+    Hashtag,
+    /// A backtick code span, matched by [`Protect::BACKTICKS`]
+    Code,
+}
+
+/// A span of the input left unstyled
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProtectedSpan {
+    /// What the span contains
+    pub kind: SpanKind,
+    /// Byte range in the input text
+    pub range: Range<usize>,
+}
+
+/// Output of [`style_text_smart`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmartStyled {
+    /// The styled text, with protected spans copied verbatim
+    pub text: String,
+    /// The spans that were preserved, in input order
+    pub preserved: Vec<ProtectedSpan>,
+}
+
+/// Style text while leaving URLs, mentions, hashtags and code spans intact
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{style_text_smart, Protect, SpanKind, Style};
+///
+/// let result = style_text_smart(
+///     "New at https://example.com by @ana",
+///     Style::MathBold,
+///     Protect::URLS | Protect::MENTIONS,
+/// );
+/// assert_eq!(result.text, "𝐍𝐞𝐰 𝐚𝐭 https://example.com 𝐛𝐲 @ana");
+/// assert_eq!(result.preserved[0].kind, SpanKind::Url);
+/// assert_eq!(result.preserved[1].range, 30..34);
+/// ```
+pub fn style_text_smart<M: CharMap>(text: &str, style: M, protect: Protect) -> SmartStyled {
+    let policy = style.accent_policy();
+    let separator = style.separator();
+    let preserved = find_protected(text, protect);
+    let mut out = String::with_capacity(text.len() * 4);
+    let mut pos = 0;
+    for span in preserved
+        .iter()
+        .map(|span| span.range.clone())
+        .chain(std::iter::once(text.len()..text.len()))
+    {
+        let gap = style_clusters(
+            &text[pos..span.start],
+            &style,
+            policy,
+            separator,
+            |_, cluster, out| out.push_str(cluster),
+        );
+        out.push_str(&gap);
+        out.push_str(&text[span.clone()]);
+        pos = span.end;
+    }
+    SmartStyled {
+        text: out,
+        preserved,
+    }
+}
+
+/// Find the spans [`style_text_smart`] would protect
+///
+/// Spans never overlap. Code spans take precedence, so a URL inside backticks
+/// is reported once as [`SpanKind::Code`].
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{find_protected, Protect, SpanKind};
+///
+/// let spans = find_protected("run `cargo doc` #rustlang", Protect::ALL);
+/// assert_eq!(spans[0].kind, SpanKind::Code);
+/// assert_eq!(spans[1].range, 16..25);
+/// ```
+pub fn find_protected(text: &str, protect: Protect) -> Vec<ProtectedSpan> {
+    let mut spans = Vec::new();
+    let mut pos = 0;
+    while let Some(c) = text[pos..].chars().next() {
+        let at_boundary = text[..pos]
+            .chars()
+            .next_back()
+            .map_or(true, |prev| !is_word_char(prev));
+        let found = match c {
+            '`' if protect.contains(Protect::BACKTICKS) => {
+                scan_code(text, pos).map(|end| (SpanKind::Code, end))
+            }
+            '@' if at_boundary && protect.contains(Protect::MENTIONS) => {
+                scan_mention(text, pos).map(|end| (SpanKind::Mention, end))
+            }
+            '#' if at_boundary && protect.contains(Protect::HASHTAGS) => {
+                scan_hashtag(text, pos).map(|end| (SpanKind::Hashtag, end))
+            }
+            _ if at_boundary && protect.contains(Protect::URLS) => {
+                scan_url(text, pos).map(|end| (SpanKind::Url, end))
+            }
+            _ => None,
+        };
+        match found {
+            Some((kind, end)) => {
+                spans.push(ProtectedSpan {
+                    kind,
+                    range: pos..end,
+                });
+                pos = end;
+            }
+            None => {
+                // A run of backticks without a closing run is plain text
+                let skip = if c == '`' {
+                    text[pos..].len() - text[pos..].trim_start_matches('`').len()
+                } else {
+                    c.len_utf8()
+                };
+                pos += skip;
+            }
+        }
+    }
+    spans
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Match a run of N backticks with the next run of exactly N backticks
+pub(crate) fn scan_code(text: &str, start: usize) -> Option<usize> {
+    let rest = &text[start..];
+    let ticks = rest.len() - rest.trim_start_matches('`').len();
+    let mut pos = start + ticks;
+    while let Some(found) = text[pos..].find('`') {
+        let run_start = pos + found;
+        let run = &text[run_start..];
+        let run_len = run.len() - run.trim_start_matches('`').len();
+        if run_len == ticks {
+            return Some(run_start + run_len);
+        }
+        pos = run_start + run_len;
+    }
+    None
+}
+
+fn scan_mention(text: &str, start: usize) -> Option<usize> {
+    let handle_end = scan_while(text, start + 1, |c| c.is_ascii_alphanumeric() || c == '_');
+    if handle_end == start + 1 {
+        return None;
+    }
+    // Federated handles: @user@instance.example
+    if text[handle_end..].starts_with('@') {
+        let domain_end = trim_trailing(
+            text,
+            handle_end + 1,
+            scan_while(text, handle_end + 1, |c| {
+                c.is_ascii_alphanumeric() || c == '.' || c == '-'
+            }),
+            |c| c == '.' || c == '-',
+        );
+        if text[handle_end + 1..domain_end].contains('.') {
+            return Some(domain_end);
+        }
+    }
+    Some(handle_end)
+}
+
+fn scan_hashtag(text: &str, start: usize) -> Option<usize> {
+    let end = scan_while(text, start + 1, is_word_char);
+    let tag = &text[start + 1..end];
+    // Require at least one non-digit so "#1" stays plain text
+    if tag.chars().any(|c| !c.is_ascii_digit()) {
+        Some(end)
+    } else {
+        None
+    }
+}
+
+const URL_PREFIXES: &[&str] = &["https://", "http://", "ftp://", "mailto:", "www."];
+
+fn scan_url(text: &str, start: usize) -> Option<usize> {
+    let rest = &text[start..];
+    let prefix = URL_PREFIXES.iter().find(|p| {
+        rest.len() >= p.len()
+            && rest.is_char_boundary(p.len())
+            && rest[..p.len()].eq_ignore_ascii_case(p)
+    })?;
+    let body_start = start + prefix.len();
+    let end = scan_while(text, body_start, |c| {
+        !c.is_whitespace() && !matches!(c, '<' | '>' | '"' | '`')
+    });
+    let end = trim_trailing(text, body_start, end, |c| {
+        matches!(c, '.' | ',' | ';' | ':' | '!' | '?' | '\'' | '*' | '_')
+    });
+    let end = trim_unbalanced_parens(text, body_start, end);
+    if end == body_start {
+        None
+    } else {
+        Some(end)
+    }
+}
+
+fn scan_while(text: &str, start: usize, accept: impl Fn(char) -> bool) -> usize {
+    text[start..]
+        .char_indices()
+        .find(|&(_, c)| !accept(c))
+        .map_or(text.len(), |(i, _)| start + i)
+}
+
+fn trim_trailing(text: &str, start: usize, end: usize, strip: impl Fn(char) -> bool) -> usize {
+    start + text[start..end].trim_end_matches(strip).len()
+}
+
+/// Drop trailing `)` that close a parenthesis opened before the URL
+fn trim_unbalanced_parens(text: &str, start: usize, mut end: usize) -> usize {
+    while text[start..end].ends_with(')')
+        && text[start..end].matches(')').count() > text[start..end].matches('(').count()
+    {
+        end -= 1;
+        end = trim_trailing(text, start, end, |c| matches!(c, '.' | ',' | ';' | ':'));
+    }
+    end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Style;
+
+    fn kinds(text: &str) -> Vec<(SpanKind, &str)> {
+        find_protected(text, Protect::ALL)
+            .into_iter()
+            .map(|span| (span.kind, &text[span.range]))
+            .collect()
+    }
+
+    #[test]
+    fn test_urls_trim_punctuation() {
+        assert_eq!(
+            kinds("see https://a.io/x. (or www.b.org/wiki_(c))!"),
+            vec![
+                (SpanKind::Url, "https://a.io/x"),
+                (SpanKind::Url, "www.b.org/wiki_(c)"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mentions_and_hashtags() {
+        assert_eq!(
+            kinds("hi @ana, @bo@mastodon.social. mail a@b.c #rust #1"),
+            vec![
+                (SpanKind::Mention, "@ana"),
+                (SpanKind::Mention, "@bo@mastodon.social"),
+                (SpanKind::Hashtag, "#rust"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_code_spans() {
+        assert_eq!(
+            kinds("a ``x ` y`` b `https://c.d` `open"),
+            vec![
+                (SpanKind::Code, "``x ` y``"),
+                (SpanKind::Code, "`https://c.d`"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_disabled_kinds_are_styled() {
+        let result = style_text_smart("#tag @me", Style::Monospace, Protect::HASHTAGS);
+        assert_eq!(result.text, "#tag @𝚖𝚎");
+        assert_eq!(result.preserved.len(), 1);
+    }
+}
@@ -1,3 +1,7 @@
+use crate::charmap::{push_cluster_parts, style_clusters, CharMap, Mapped};
+use std::fmt;
+use std::str::FromStr;
+
 /// Unicode text style variants
 ///
 /// Each style transforms standard ASCII/Latin characters into their Unicode equivalents.
@@ -5,21 +9,30 @@
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub enum Style {
-    /// Mathematical bold (𝐀𝐁𝐂𝐚𝐛𝐜𝟎𝟏𝟐)
+    /// Mathematical bold (𝐀𝐁𝐂𝐚𝐛𝐜𝟎𝟏𝟐𝚨𝛂𝛛)
     MathBold,
 
-    /// Mathematical italic (𝐴𝐵𝐶𝑎𝑏𝑐)
+    /// Mathematical italic (𝐴𝐵𝐶𝑎𝑏𝑐𝛢𝛼𝜕)
     Italic,
 
-    /// Mathematical bold italic (𝑨𝑩𝑪𝒂𝒃𝒄)
+    /// Mathematical bold italic (𝑨𝑩𝑪𝒂𝒃𝒄𝜜𝜶𝝏)
     BoldItalic,
 
     /// Fullwidth forms (ＡＢＣａｂｃ０１２)
     Fullwidth,
 
-    /// Small capitals (ᴀʙᴄ)
+    /// Small capitals for lowercase letters, with capitals kept (Rᴜꜱᴛ)
+    ///
+    /// See [`Style::PetiteCaps`] for the substitutions used.
     SmallCaps,
 
+    /// Small capitals for every letter (ʀᴜꜱᴛ)
+    ///
+    /// Most forms are IPA letters. `ꜰ` and `ꜱ` come from Latin Extended-D
+    /// instead, `ꞯ` (Unicode 11) is missing from many fonts, and `x`, which
+    /// has no small capital, is kept as the lowercase `x` of the same shape.
+    PetiteCaps,
+
     /// Circled Latin letters (ⒶⒷⒸⒶⒷⒸ)
     Circled,
 
@@ -44,7 +57,7 @@ pub enum Style {
     /// Bold script (𝓐𝓑𝓒𝓪𝓫𝓬)
     ScriptBold,
 
-    /// Double-struck / Blackboard bold (𝔸𝔹ℂ𝕒𝕓𝕔𝟘𝟙𝟚)
+    /// Double-struck / Blackboard bold (𝔸𝔹ℂ𝕒𝕓𝕔𝟘𝟙𝟚ℾℿℽℼ)
     DoubleStruck,
 
     /// Monospace (𝙰𝙱𝙲𝚊𝚋𝚌𝟶𝟷𝟸)
@@ -53,13 +66,13 @@ pub enum Style {
     /// Sans-serif (𝖠𝖡𝖢𝖺𝖻𝖼𝟢𝟣𝟤)
     SansSerif,
 
-    /// Sans-serif bold (𝗔𝗕𝗖𝗮𝗯𝗰𝟬𝟭𝟮)
+    /// Sans-serif bold (𝗔𝗕𝗖𝗮𝗯𝗰𝟬𝟭𝟮𝝖𝝰𝞉)
     SansSerifBold,
 
     /// Sans-serif italic (𝘈𝘉𝘊𝘢𝘣𝘤)
     SansSerifItalic,
 
-    /// Sans-serif bold italic (𝘼𝘽𝘾𝙖𝙗𝙘)
+    /// Sans-serif bold italic (𝘼𝘽𝘾𝙖𝙗𝙘𝞐𝞪𝟃)
     SansSerifBoldItalic,
 
     /// Parenthesized Latin letters (⒜⒝⒞)
@@ -74,12 +87,317 @@ pub enum Style {
     /// Strikethrough using combining character (A̶B̶C̶)
     Strikethrough,
 
-    /// Inverted/flipped text (ɐqɔ)
+    /// Inverted/flipped text, reversed to read upside down (ɔqɐ)
     Inverted,
+
+    /// Regional indicator letters (🇷‌🇺‌🇸‌🇹), separated so pairs never form flags
+    ///
+    /// A [`Separator`] goes between adjacent letters; use [`flag`](crate::flag)
+    /// to build real flags.
+    RegionalIndicator,
+}
+
+/// Invisible character placed between adjacent regional indicator letters
+///
+/// Without it, pairs such as `US` fuse into flag emoji.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Separator {
+    /// U+200C ZERO WIDTH NON-JOINER, which keeps the word a single line-break unit
+    #[default]
+    ZeroWidthNonJoiner,
+
+    /// U+200B ZERO WIDTH SPACE, for platforms that ignore ZWNJ between emoji;
+    /// allows line breaks between letters
+    ZeroWidthSpace,
+}
+
+impl Separator {
+    /// The separator character
+    pub const fn as_char(self) -> char {
+        match self {
+            Separator::ZeroWidthNonJoiner => '\u{200C}',
+            Separator::ZeroWidthSpace => '\u{200B}',
+        }
+    }
+}
+
+/// How a style treats accented and extended Latin letters
+///
+/// Letters such as `é` or `ø` are decomposed into a base letter and combining
+/// marks (or an explicit substitute such as `ß` → `ss`) before styling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccentPolicy {
+    /// Style the base letter and re-attach the marks (𝐞́)
+    Combine,
+
+    /// Style the base letter and drop the marks (ⓔ)
+    Strip,
+
+    /// Leave the letter unstyled (é)
+    Preserve,
+}
+
+/// How likely a style is to render as missing-glyph boxes ("tofu")
+///
+/// Ordered from least to most risky, so `risk >= SupportRisk::High` works.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SupportRisk {
+    /// Long-standing Basic Multilingual Plane characters with broad font support
+    Low,
+
+    /// Mathematical Alphanumeric Symbols, which need a supplementary-plane font
+    Medium,
+
+    /// Enclosed Alphanumeric Supplement or Unicode 11+ characters, often tofu on older systems
+    High,
+}
+
+/// Error returned when a style name is not recognized
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownStyle(pub String);
+
+impl fmt::Display for UnknownStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown style: '{}'", self.0)
+    }
+}
+
+impl std::error::Error for UnknownStyle {}
+
+impl Style {
+    /// Every style variant, in declaration order
+    pub const ALL: &'static [Style] = &[
+        Style::MathBold,
+        Style::Italic,
+        Style::BoldItalic,
+        Style::Fullwidth,
+        Style::SmallCaps,
+        Style::PetiteCaps,
+        Style::Circled,
+        Style::CircledNegative,
+        Style::Squared,
+        Style::SquaredNegative,
+        Style::Fraktur,
+        Style::FrakturBold,
+        Style::Script,
+        Style::ScriptBold,
+        Style::DoubleStruck,
+        Style::Monospace,
+        Style::SansSerif,
+        Style::SansSerifBold,
+        Style::SansSerifItalic,
+        Style::SansSerifBoldItalic,
+        Style::Parenthesized,
+        Style::Superscript,
+        Style::Subscript,
+        Style::Strikethrough,
+        Style::Inverted,
+        Style::RegionalIndicator,
+    ];
+
+    /// Name, description and support risk
+    const fn metadata(self) -> (&'static str, &'static str, SupportRisk) {
+        use SupportRisk::*;
+        match self {
+            Style::MathBold => ("math-bold", "Mathematical bold", Medium),
+            Style::Italic => ("italic", "Mathematical italic", Medium),
+            Style::BoldItalic => ("bold-italic", "Mathematical bold italic", Medium),
+            Style::Fullwidth => ("fullwidth", "East Asian fullwidth forms", Low),
+            Style::SmallCaps => ("small-caps", "Small capitals, capitals kept", High),
+            Style::PetiteCaps => ("petite-caps", "Small capitals for every letter", High),
+            Style::Circled => ("circled", "Circled letters and digits", Low),
+            Style::CircledNegative => ("circled-negative", "Negative circled capitals", High),
+            Style::Squared => ("squared", "Squared capitals", High),
+            Style::SquaredNegative => ("squared-negative", "Negative squared capitals", High),
+            Style::Fraktur => ("fraktur", "Fraktur / Gothic", Medium),
+            Style::FrakturBold => ("fraktur-bold", "Bold Fraktur", Medium),
+            Style::Script => ("script", "Script / Calligraphic", Medium),
+            Style::ScriptBold => ("script-bold", "Bold script", Medium),
+            Style::DoubleStruck => ("double-struck", "Double-struck / Blackboard bold", Medium),
+            Style::Monospace => ("monospace", "Mathematical monospace", Medium),
+            Style::SansSerif => ("sans-serif", "Mathematical sans-serif", Medium),
+            Style::SansSerifBold => ("sans-serif-bold", "Sans-serif bold", Medium),
+            Style::SansSerifItalic => ("sans-serif-italic", "Sans-serif italic", Medium),
+            Style::SansSerifBoldItalic => {
+                ("sans-serif-bold-italic", "Sans-serif bold italic", Medium)
+            }
+            Style::Parenthesized => ("parenthesized", "Parenthesized lowercase letters", Low),
+            Style::Superscript => ("superscript", "Superscript digits and operators", Low),
+            Style::Subscript => ("subscript", "Subscript letters, digits and operators", Low),
+            Style::Strikethrough => ("strikethrough", "Combining long stroke overlay", Low),
+            Style::Inverted => ("inverted", "Upside-down letters", Low),
+            Style::RegionalIndicator => ("regional-indicator", "Regional indicator letters", High),
+        }
+    }
+
+    /// Stable kebab-case name, as accepted by [`FromStr`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use prettychars::Style;
+    ///
+    /// assert_eq!(Style::SansSerifBold.name(), "sans-serif-bold");
+    /// assert_eq!("sans-serif-bold".parse(), Ok(Style::SansSerifBold));
+    /// ```
+    pub const fn name(self) -> &'static str {
+        self.metadata().0
+    }
+
+    /// Short human-readable description
+    pub const fn description(self) -> &'static str {
+        self.metadata().1
+    }
+
+    /// How likely this style is to render as tofu on older systems
+    ///
+    /// Rated by the least widely supported character the style can produce.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use prettychars::{Style, SupportRisk};
+    ///
+    /// assert_eq!(Style::Fullwidth.support_risk(), SupportRisk::Low);
+    /// assert_eq!(Style::Squared.support_risk(), SupportRisk::High);
+    /// ```
+    pub const fn support_risk(self) -> SupportRisk {
+        self.metadata().2
+    }
+
+    /// This style applied to `"AaBb12"`, for previews
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use prettychars::Style;
+    ///
+    /// assert_eq!(Style::MathBold.sample(), "𝐀𝐚𝐁𝐛𝟏𝟐");
+    /// ```
+    pub fn sample(self) -> String {
+        style("AaBb12", self)
+    }
+
+    /// Default accent handling for this style
+    ///
+    /// Enclosed, raised and inverted forms render combining marks poorly, so
+    /// they drop them; `Strikethrough` already applies to any character.
+    pub fn accent_policy(self) -> AccentPolicy {
+        match self {
+            Style::Circled
+            | Style::CircledNegative
+            | Style::Squared
+            | Style::SquaredNegative
+            | Style::Parenthesized
+            | Style::Superscript
+            | Style::Subscript
+            | Style::Inverted
+            | Style::RegionalIndicator => AccentPolicy::Strip,
+            Style::Strikethrough => AccentPolicy::Preserve,
+            _ => AccentPolicy::Combine,
+        }
+    }
+
+    /// Whether this style transforms `c`
+    ///
+    /// Accented letters count as supported when their decomposition can be styled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use prettychars::Style;
+    ///
+    /// assert!(Style::Squared.supports('A'));
+    /// assert!(!Style::Squared.supports('a'));
+    /// assert!(Style::MathBold.supports('é'));
+    /// ```
+    pub fn supports(self, c: char) -> bool {
+        push_cluster_parts(&self, c, "", self.accent_policy(), &mut String::new())
+    }
+
+    /// Iterator over every single-character mapping as `(input, output)` pairs
+    ///
+    /// Decomposed letters and `Strikethrough` (which appends a combining
+    /// character) produce more than one character and are not listed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use prettychars::Style;
+    ///
+    /// assert_eq!(Style::Parenthesized.coverage().count(), 26);
+    /// assert!(Style::Circled.coverage().any(|pair| pair == ('0', '⓪')));
+    /// ```
+    pub fn coverage(self) -> impl Iterator<Item = (char, char)> {
+        MAPPED_INPUTS
+            .iter()
+            .flat_map(|range| range.clone())
+            .filter_map(move |c| get_mapping(c, self).map(|mapped| (c, mapped)))
+    }
 }
 
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Style {
+    type Err = UnknownStyle;
+
+    /// Parse a kebab-case style name, ignoring ASCII case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Style::ALL
+            .iter()
+            .copied()
+            .find(|style| style.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| UnknownStyle(s.to_string()))
+    }
+}
+
+/// Every input character accepted by at least one `map_*` function
+const MAPPED_INPUTS: &[std::ops::RangeInclusive<char>] = &[
+    ' '..='~',
+    'ı'..='ı',
+    'ȷ'..='ȷ',
+    'Α'..='Ω',
+    'α'..='ω',
+    'ϑ'..='ϑ',
+    'ϕ'..='ϖ',
+    'Ϝ'..='ϝ',
+    'ϰ'..='ϱ',
+    'ϴ'..='ϵ',
+    '∂'..='∂',
+    '∇'..='∇',
+];
+
+/// Error returned by [`try_style`] when some characters have no mapping
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StyleError {
+    /// Style that was being applied
+    pub style: Style,
+    /// Byte offset and character of each unmapped character, in order
+    pub unmapped: Vec<(usize, char)>,
+}
+
+impl fmt::Display for StyleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Style '{}' has no mapping for", self.style)?;
+        for (i, (offset, c)) in self.unmapped.iter().enumerate() {
+            let sep = if i == 0 { "" } else { "," };
+            write!(f, "{} {:?} at byte {}", sep, c, offset)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for StyleError {}
+
 /// Convert text to a Unicode style
 ///
+/// Accepts any [`CharMap`]: a built-in [`Style`], a `const` lookup table, or a
+/// type defined with [`char_map!`](crate::char_map).
+///
 /// # Examples
 ///
 /// ```
@@ -90,6 +408,27 @@ pub enum Style {
 /// assert_eq!(style("123", Style::DoubleStruck), "𝟙𝟚𝟛");
 /// ```
 ///
+/// Accented letters are decomposed and styled according to
+/// [`Style::accent_policy`]:
+///
+/// ```
+/// use prettychars::{style, Style};
+///
+/// assert_eq!(style("café", Style::MathBold), "𝐜𝐚𝐟𝐞\u{0301}");
+/// assert_eq!(style("café", Style::Circled), "ⓒⓐⓕⓔ");
+/// ```
+///
+/// Text is processed by grapheme cluster: existing combining marks stay on
+/// their base letter, effects attach once per cluster, and emoji sequences
+/// (ZWJ, skin tones, flags) are never altered:
+///
+/// ```
+/// use prettychars::{style, Style};
+///
+/// assert_eq!(style("e\u{0301}", Style::Strikethrough), "e\u{0301}\u{0336}");
+/// assert_eq!(style("hi 👍🏽", Style::Strikethrough), "h\u{0336}i\u{0336} 👍🏽");
+/// ```
+///
 /// Characters without mappings are passed through unchanged:
 ///
 /// ```
@@ -98,8 +437,55 @@ pub enum Style {
 /// // Emoji has no bold variant, passed through
 /// assert_eq!(style("Hi 👋", Style::MathBold), "𝐇𝐢 👋");
 /// ```
-pub fn style(text: &str, style: Style) -> String {
-    text.chars().map(|c| convert_char(c, style)).collect()
+pub fn style<M: CharMap>(text: &str, style: M) -> String {
+    let separator = style.separator();
+    style_clusters(
+        text,
+        &style,
+        style.accent_policy(),
+        separator,
+        |_, cluster, out| out.push_str(cluster),
+    )
+}
+
+/// Convert text to a Unicode style, failing if any character has no mapping
+///
+/// Whitespace is never reported.
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{try_style, Style};
+///
+/// assert_eq!(try_style("OK", Style::Squared).unwrap(), "🄾🄺");
+///
+/// let err = try_style("Ok!", Style::Squared).unwrap_err();
+/// assert_eq!(err.unmapped, vec![(1, 'k'), (2, '!')]);
+/// ```
+///
+/// # Errors
+///
+/// Returns [`StyleError`] listing the byte offset of every unmapped character.
+pub fn try_style(text: &str, style: Style) -> Result<String, StyleError> {
+    let mut unmapped = Vec::new();
+    let out = style_clusters(
+        text,
+        &style,
+        style.accent_policy(),
+        style.separator(),
+        |offset, cluster, out| {
+            if let Some(base) = cluster.chars().next().filter(|c| !c.is_whitespace()) {
+                unmapped.push((offset, base));
+            }
+            out.push_str(cluster);
+        },
+    );
+    if unmapped.is_empty() {
+        Ok(out)
+    } else {
+        unmapped.sort_unstable();
+        Err(StyleError { style, unmapped })
+    }
 }
 
 /// List all available style variants
@@ -114,49 +500,27 @@ pub fn style(text: &str, style: Style) -> String {
 /// }
 /// ```
 pub fn style_names() -> impl Iterator<Item = Style> {
-    use Style::*;
-    [
-        MathBold,
-        Italic,
-        BoldItalic,
-        Fullwidth,
-        SmallCaps,
-        Circled,
-        CircledNegative,
-        Squared,
-        SquaredNegative,
-        Fraktur,
-        FrakturBold,
-        Script,
-        ScriptBold,
-        DoubleStruck,
-        Monospace,
-        SansSerif,
-        SansSerifBold,
-        SansSerifItalic,
-        SansSerifBoldItalic,
-        Parenthesized,
-        Superscript,
-        Subscript,
-        Strikethrough,
-        Inverted,
-    ]
-    .into_iter()
-}
-
-fn convert_char(c: char, style: Style) -> String {
-    match style {
-        Style::Strikethrough => {
-            if c.is_whitespace() {
-                c.to_string()
-            } else {
-                format!("{}\u{0336}", c)
-            }
+    Style::ALL.iter().copied()
+}
+
+impl CharMap for Style {
+    fn map_char(&self, c: char) -> Option<Mapped> {
+        match self {
+            Style::Strikethrough if !c.is_whitespace() => Some(Mapped::Append('\u{0336}')),
+            _ => get_mapping(c, *self).map(Mapped::Char),
         }
-        _ => match get_mapping(c, style) {
-            Some(mapped) => mapped.to_string(),
-            None => c.to_string(),
-        },
+    }
+
+    fn accent_policy(&self) -> AccentPolicy {
+        Style::accent_policy(*self)
+    }
+
+    fn reversed(&self) -> bool {
+        *self == Style::Inverted
+    }
+
+    fn separator(&self) -> Option<char> {
+        (*self == Style::RegionalIndicator).then_some(Separator::default().as_char())
     }
 }
 
@@ -167,6 +531,7 @@ fn get_mapping(c: char, style: Style) -> Option<char> {
         Style::BoldItalic => map_bold_italic(c),
         Style::Fullwidth => map_fullwidth(c),
         Style::SmallCaps => map_small_caps(c),
+        Style::PetiteCaps => map_petite_caps(c),
         Style::Circled => map_circled(c),
         Style::CircledNegative => map_circled_negative(c),
         Style::Squared => map_squared(c),
@@ -185,16 +550,44 @@ fn get_mapping(c: char, style: Style) -> Option<char> {
         Style::Superscript => map_superscript(c),
         Style::Subscript => map_subscript(c),
         Style::Inverted => map_inverted(c),
-        Style::Strikethrough => unreachable!(),
+        Style::RegionalIndicator => map_regional_indicator(c),
+        Style::Strikethrough => None,
     }
 }
 
+/// Offset of a Greek letter or symbol within a Mathematical Alphanumeric Greek block
+///
+/// Each block holds the capitals (with ϴ in the unused final-sigma slot), ∇,
+/// the lowercase letters, ∂, and the variant forms ϵ ϑ ϰ ϕ ϱ ϖ.
+fn greek_offset(c: char) -> Option<u32> {
+    Some(match c {
+        'Α'..='Ρ' | 'Σ'..='Ω' => c as u32 - 'Α' as u32,
+        'ϴ' => 17,
+        '∇' => 25,
+        'α'..='ω' => 26 + (c as u32 - 'α' as u32),
+        '∂' => 51,
+        'ϵ' => 52,
+        'ϑ' => 53,
+        'ϰ' => 54,
+        'ϕ' => 55,
+        'ϱ' => 56,
+        'ϖ' => 57,
+        _ => return None,
+    })
+}
+
+fn map_greek(c: char, base: u32) -> Option<char> {
+    char::from_u32(base + greek_offset(c)?)
+}
+
 fn map_mathbold(c: char) -> Option<char> {
     Some(match c {
         'A'..='Z' => char::from_u32(0x1D400 + (c as u32 - 'A' as u32))?,
         'a'..='z' => char::from_u32(0x1D41A + (c as u32 - 'a' as u32))?,
         '0'..='9' => char::from_u32(0x1D7CE + (c as u32 - '0' as u32))?,
-        _ => return None,
+        'Ϝ' => '𝟊',
+        'ϝ' => '𝟋',
+        _ => return map_greek(c, 0x1D6A8),
     })
 }
 
@@ -208,7 +601,9 @@ fn map_italic(c: char) -> Option<char> {
                 char::from_u32(0x1D44E + (c as u32 - 'a' as u32))?
             }
         }
-        _ => return None,
+        'ı' => '𝚤',
+        'ȷ' => '𝚥',
+        _ => return map_greek(c, 0x1D6E2),
     })
 }
 
@@ -216,7 +611,7 @@ fn map_bold_italic(c: char) -> Option<char> {
     Some(match c {
         'A'..='Z' => char::from_u32(0x1D468 + (c as u32 - 'A' as u32))?,
         'a'..='z' => char::from_u32(0x1D482 + (c as u32 - 'a' as u32))?,
-        _ => return None,
+        _ => return map_greek(c, 0x1D71C),
     })
 }
 
@@ -229,6 +624,14 @@ fn map_fullwidth(c: char) -> Option<char> {
 }
 
 fn map_small_caps(c: char) -> Option<char> {
+    match c {
+        'A'..='Z' => Some(c),
+        _ if c.is_ascii_lowercase() => map_petite_caps(c),
+        _ => None,
+    }
+}
+
+fn map_petite_caps(c: char) -> Option<char> {
     Some(match c {
         'A' | 'a' => 'ᴀ',
         'B' | 'b' => 'ʙ',
@@ -253,6 +656,7 @@ fn map_small_caps(c: char) -> Option<char> {
         'U' | 'u' => 'ᴜ',
         'V' | 'v' => 'ᴠ',
         'W' | 'w' => 'ᴡ',
+        'X' | 'x' => 'x',
         'Y' | 'y' => 'ʏ',
         'Z' | 'z' => 'ᴢ',
         _ => return None,
@@ -327,6 +731,10 @@ fn map_double_struck(c: char) -> Option<char> {
         'A'..='Z' => char::from_u32(0x1D538 + (c as u32 - 'A' as u32))?,
         'a'..='z' => char::from_u32(0x1D552 + (c as u32 - 'a' as u32))?,
         '0'..='9' => char::from_u32(0x1D7D8 + (c as u32 - '0' as u32))?,
+        'Γ' => 'ℾ',
+        'Π' => 'ℿ',
+        'γ' => 'ℽ',
+        'π' => 'ℼ',
         _ => return None,
     })
 }
@@ -354,7 +762,7 @@ fn map_sans_serif_bold(c: char) -> Option<char> {
         'A'..='Z' => char::from_u32(0x1D5D4 + (c as u32 - 'A' as u32))?,
         'a'..='z' => char::from_u32(0x1D5EE + (c as u32 - 'a' as u32))?,
         '0'..='9' => char::from_u32(0x1D7EC + (c as u32 - '0' as u32))?,
-        _ => return None,
+        _ => return map_greek(c, 0x1D756),
     })
 }
 
@@ -370,7 +778,7 @@ fn map_sans_serif_bold_italic(c: char) -> Option<char> {
     Some(match c {
         'A'..='Z' => char::from_u32(0x1D63C + (c as u32 - 'A' as u32))?,
         'a'..='z' => char::from_u32(0x1D656 + (c as u32 - 'a' as u32))?,
-        _ => return None,
+        _ => return map_greek(c, 0x1D790),
     })
 }
 
@@ -467,6 +875,14 @@ fn map_inverted(c: char) -> Option<char> {
     })
 }
 
+fn map_regional_indicator(c: char) -> Option<char> {
+    Some(match c {
+        'A'..='Z' => char::from_u32(0x1F1E6 + (c as u32 - 'A' as u32))?,
+        'a'..='z' => char::from_u32(0x1F1E6 + (c as u32 - 'a' as u32))?,
+        _ => return None,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -499,8 +915,93 @@ mod tests {
         assert!(result.contains('\u{0336}'));
     }
 
+    #[test]
+    fn test_greek() {
+        assert_eq!(style("ΑΩαω", Style::MathBold), "𝚨𝛀𝛂𝛚");
+        assert_eq!(style("∇×E = -∂B/∂t", Style::Italic), "𝛻×𝐸 = -𝜕𝐵/𝜕𝑡");
+        assert_eq!(style("ϴϑϕϖϵ", Style::BoldItalic), "𝜭𝝑𝝓𝝕𝝐");
+        assert_eq!(style("Σς", Style::SansSerifBold), "𝝨𝞁");
+        assert_eq!(style("Λλ", Style::SansSerifBoldItalic), "𝞚𝞴");
+        assert_eq!(style("Ϝϝ", Style::MathBold), "𝟊𝟋");
+        assert_eq!(style("ıȷ", Style::Italic), "𝚤𝚥");
+        assert_eq!(style("Γπ", Style::DoubleStruck), "ℾℼ");
+        // Greek has no sans-serif regular variant
+        assert_eq!(style("α", Style::SansSerif), "α");
+    }
+
+    #[test]
+    fn test_accented_latin() {
+        assert_eq!(style("café", Style::MathBold), "𝐜𝐚𝐟𝐞\u{0301}");
+        assert_eq!(style("Ǖ", Style::Monospace), "𝚄\u{0308}\u{0304}");
+        assert_eq!(style("straße", Style::SansSerif), "𝗌𝗍𝗋𝖺𝗌𝗌𝖾");
+        assert_eq!(
+            style("Łódź", Style::Fullwidth),
+            "Ｌ\u{0337}ｏ\u{0301}ｄｚ\u{0301}"
+        );
+    }
+
+    #[test]
+    fn test_accent_policies() {
+        assert_eq!(style("NAÏVE", Style::Squared), "🄽🄰🄸🅅🄴");
+        assert_eq!(style("é", Style::Strikethrough), "é\u{0336}");
+        // Base letter without a mapping leaves the letter untouched
+        assert_eq!(style("É", Style::Parenthesized), "É");
+    }
+
+    #[test]
+    fn test_coverage_lists_every_mapping() {
+        for style in style_names() {
+            let covered: Vec<char> = style.coverage().map(|(c, _)| c).collect();
+            // Every mapped input must be listed in `MAPPED_INPUTS`
+            for c in ('\0'..='\u{FFFF}').filter(|&c| get_mapping(c, style).is_some()) {
+                assert!(covered.contains(&c), "{:?} misses {:?}", style, c);
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_style_error() {
+        let err = try_style("a é?", Style::Italic).unwrap_err();
+        assert_eq!(err.unmapped, vec![(4, '?')]);
+        assert_eq!(
+            err.to_string(),
+            "Style 'italic' has no mapping for '?' at byte 4"
+        );
+    }
+
+    #[test]
+    fn test_name_round_trip() {
+        for &style in Style::ALL {
+            assert_eq!(style.to_string().parse::<Style>(), Ok(style));
+        }
+        assert_eq!("Math-Bold".parse(), Ok(Style::MathBold));
+        assert_eq!(
+            "bold".parse::<Style>(),
+            Err(UnknownStyle("bold".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_small_caps_modes() {
+        assert_eq!(style("Rust", Style::SmallCaps), "Rᴜꜱᴛ");
+        assert_eq!(style("Rust", Style::PetiteCaps), "ʀᴜꜱᴛ");
+        assert_eq!(style("Fix Quoted Xs", Style::SmallCaps), "Fɪx Qᴜᴏᴛᴇᴅ Xꜱ");
+        assert_eq!(style("Éric", Style::SmallCaps), "Éʀɪᴄ");
+        // Kept capitals count as supported
+        assert!(try_style("Rust", Style::SmallCaps).is_ok());
+        assert!(Style::SmallCaps.supports('R') && Style::SmallCaps.supports('É'));
+        assert!(Style::SmallCaps.coverage().any(|pair| pair == ('R', 'R')));
+        assert!(Style::PetiteCaps.supports('X'));
+    }
+
+    #[test]
+    fn test_inverted_reverses_clusters() {
+        assert_eq!(style("abc", Style::Inverted), "ɔqɐ");
+        assert_eq!(style("ae\u{0301}", Style::Inverted), "ǝ\u{0301}ɐ");
+    }
+
     #[test]
     fn test_style_names_count() {
-        assert_eq!(style_names().count(), 24);
+        assert_eq!(style_names().count(), 26);
     }
 }
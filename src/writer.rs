@@ -0,0 +1,452 @@
+use crate::charmap::{push_cluster, style_clusters, CharMap};
+use crate::grapheme::graphemes;
+use crate::styles::{AccentPolicy, Style};
+use std::io::{self, Write};
+
+/// Longest cluster or reversed line held back; anything longer is written out
+const MAX_PENDING: usize = 4096;
+
+/// Longest escape sequence passed through; anything longer is plain text
+const MAX_ESCAPE: usize = 1024;
+
+/// An [`io::Write`] adapter that styles text on its way to another writer
+///
+/// Input is UTF-8 and may arrive in arbitrary pieces: a character or grapheme
+/// cluster split across `write` calls is held back until it is complete, so
+/// the output matches [`style`](crate::style) on the whole text. Bytes that
+/// are not valid UTF-8 are passed through unchanged. Reversed styles such as
+/// [`Style::Inverted`] reverse each line; a line over a few kilobytes is
+/// reversed in pieces.
+///
+/// The last grapheme cluster is always held back, since the next write may
+/// add marks to it. [`flush`](Write::flush) and [`finish`](StyleWriter::finish)
+/// write it out; dropping the writer finishes it and ignores errors. Output
+/// the underlying writer fails to take is kept and retried on the next call.
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{Style, StyleWriter};
+/// use std::io::Write;
+///
+/// let mut writer = StyleWriter::new(Vec::new(), Style::MathBold).preserve_ansi(true);
+/// writer.write_all(b"\x1b[32mok\x1b[0m ")?;
+/// writer.write_all("caf\u{e9}".as_bytes())?;
+/// let out = writer.finish()?;
+/// assert_eq!(String::from_utf8(out).unwrap(), "\x1b[32m𝐨𝐤\x1b[0m 𝐜𝐚𝐟𝐞\u{0301}");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct StyleWriter<W: Write, M: CharMap = Style> {
+    /// Always `Some` until [`StyleWriter::finish`] takes it
+    inner: Option<W>,
+    map: M,
+    policy: AccentPolicy,
+    separator: Option<char>,
+    preserve_ansi: bool,
+    /// Input not yet written: incomplete UTF-8, escapes or clusters
+    pending: Vec<u8>,
+    /// Styled output the underlying writer has not accepted yet
+    output: Vec<u8>,
+    /// Whether the last cluster written was styled, for separators
+    joined: bool,
+}
+
+impl<W: Write, M: CharMap> StyleWriter<W, M> {
+    /// Style everything written with `map`
+    pub fn new(inner: W, map: M) -> Self {
+        StyleWriter {
+            inner: Some(inner),
+            policy: map.accent_policy(),
+            separator: map.separator(),
+            map,
+            preserve_ansi: false,
+            pending: Vec::new(),
+            output: Vec::new(),
+            joined: false,
+        }
+    }
+
+    /// Pass ANSI escape sequences (colors, cursor movement, OSC links) through
+    /// unstyled
+    ///
+    /// Off by default, so the letters of an escape such as `ESC[1m` get styled.
+    pub fn preserve_ansi(mut self, preserve: bool) -> Self {
+        self.preserve_ansi = preserve;
+        self
+    }
+
+    /// The underlying writer
+    pub fn get_ref(&self) -> &W {
+        self.inner.as_ref().expect("writer present until finish")
+    }
+
+    /// The underlying writer, mutably
+    ///
+    /// Writing to it directly bypasses any input still held back.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner.as_mut().expect("writer present until finish")
+    }
+
+    /// Write out everything held back and return the underlying writer
+    ///
+    /// A trailing incomplete UTF-8 sequence is written unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.finish_pending()?;
+        Ok(self.inner.take().expect("writer present until finish"))
+    }
+
+    /// Style as much pending input as possible into the output buffer
+    ///
+    /// With `force`, clusters and escapes that might continue are styled too;
+    /// only an incomplete UTF-8 sequence stays pending.
+    fn process(&mut self, force: bool) {
+        let mut pending = std::mem::take(&mut self.pending);
+        let mut out = std::mem::take(&mut self.output);
+        out.reserve(pending.len() * 4);
+        let mut pos = 0;
+        while pos < pending.len() {
+            let (text, invalid) = match std::str::from_utf8(&pending[pos..]) {
+                Ok(text) => (text, None),
+                Err(e) => {
+                    let valid = &pending[pos..pos + e.valid_up_to()];
+                    let text = std::str::from_utf8(valid).expect("prefix is valid UTF-8");
+                    (text, e.error_len())
+                }
+            };
+            // Nothing continues across an invalid sequence
+            let used = self.process_text(text, force || invalid.is_some(), &mut out);
+            pos += used;
+            match invalid {
+                Some(len) if used == text.len() => {
+                    out.extend_from_slice(&pending[pos..pos + len]);
+                    self.joined = false;
+                    pos += len;
+                }
+                _ => break,
+            }
+        }
+        pending.drain(..pos);
+        self.pending = pending;
+        self.output = out;
+    }
+
+    /// Write the output buffer, keeping whatever the underlying writer did
+    /// not accept
+    fn write_output(&mut self) -> io::Result<()> {
+        let inner = self.inner.as_mut().expect("writer present until finish");
+        let mut written = 0;
+        let result = loop {
+            if written == self.output.len() {
+                break Ok(());
+            }
+            match inner.write(&self.output[written..]) {
+                Ok(0) => break Err(io::ErrorKind::WriteZero.into()),
+                Ok(n) => written += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => break Err(e),
+            }
+        };
+        self.output.drain(..written);
+        result
+    }
+
+    /// Write out everything, including an incomplete UTF-8 sequence as is
+    fn finish_pending(&mut self) -> io::Result<()> {
+        self.process(true);
+        let rest = std::mem::take(&mut self.pending);
+        self.output.extend_from_slice(&rest);
+        self.write_output()?;
+        self.get_mut().flush()
+    }
+
+    /// Style complete text and escapes, returning the number of bytes used
+    fn process_text(&mut self, text: &str, force: bool, out: &mut Vec<u8>) -> usize {
+        let mut styled = String::with_capacity(text.len() * 4);
+        let mut pos = 0;
+        while pos < text.len() {
+            let rest = &text[pos..];
+            let escape = if self.preserve_ansi {
+                rest.find('\u{1B}')
+            } else {
+                None
+            };
+            let segment_end = escape.map_or(text.len(), |i| pos + i);
+            if segment_end > pos {
+                let segment = &text[pos..segment_end];
+                let last_segment = escape.is_none();
+                // A long line for a reversed style or an endless cluster is cut
+                // short rather than held back without limit
+                let keep = match self.held_back(segment) {
+                    keep if last_segment && !force && keep <= MAX_PENDING => keep,
+                    _ => 0,
+                };
+                self.style_segment(&segment[..segment.len() - keep], &mut styled);
+                pos = segment_end - keep;
+                if keep > 0 {
+                    break;
+                }
+                continue;
+            }
+            match escape_len(rest) {
+                Some(len) => {
+                    styled.push_str(&rest[..len]);
+                    pos += len;
+                }
+                None if force => {
+                    styled.push_str(rest);
+                    pos = text.len();
+                }
+                None if rest.len() >= MAX_ESCAPE => {
+                    // Too long to be an escape: the rest is styled as text
+                    styled.push('\u{1B}');
+                    self.joined = false;
+                    pos += 1;
+                }
+                None => break,
+            }
+        }
+        out.extend_from_slice(styled.as_bytes());
+        pos
+    }
+
+    /// Length of the tail of `segment` that may still change with more input
+    fn held_back(&self, segment: &str) -> usize {
+        if self.map.reversed() {
+            // Reversed styles work on whole lines
+            return segment
+                .rfind('\n')
+                .map_or(segment.len(), |i| segment.len() - i - 1);
+        }
+        graphemes(segment).last().map_or(0, str::len)
+    }
+
+    fn style_segment(&mut self, segment: &str, out: &mut String) {
+        if self.map.reversed() {
+            for (i, line) in segment.split('\n').enumerate() {
+                if i > 0 {
+                    out.push('\n');
+                }
+                let styled =
+                    style_clusters(line, &self.map, self.policy, self.separator, |_, c, out| {
+                        out.push_str(c)
+                    });
+                out.push_str(&styled);
+            }
+            return;
+        }
+        for cluster in graphemes(segment) {
+            let start = out.len();
+            if let Some(sep) = self.separator.filter(|_| self.joined) {
+                out.push(sep);
+            }
+            self.joined = push_cluster(&self.map, cluster, self.policy, out);
+            if !self.joined {
+                out.truncate(start);
+                out.push_str(cluster);
+            }
+        }
+    }
+}
+
+/// Length of the ANSI escape sequence at the start of `text`, or `None` if it
+/// is incomplete
+///
+/// Handles CSI (`ESC [ ... final`), OSC (`ESC ] ... BEL` or `ESC ] ... ESC \`)
+/// and two-character escapes, up to [`MAX_ESCAPE`] bytes long.
+fn escape_len(text: &str) -> Option<usize> {
+    let bytes = &text.as_bytes()[..text.len().min(MAX_ESCAPE)];
+    match bytes.get(1)? {
+        b'[' => bytes[2..]
+            .iter()
+            .position(|b| (0x40..=0x7E).contains(b))
+            .map(|i| i + 3),
+        b']' => bytes[2..].iter().enumerate().find_map(|(i, &b)| match b {
+            0x07 => Some(i + 3),
+            b'\\' if bytes[i + 1] == 0x1B => Some(i + 3),
+            _ => None,
+        }),
+        _ => Some(1 + text[1..].chars().next()?.len_utf8()),
+    }
+}
+
+impl<W: Write, M: CharMap> Write for StyleWriter<W, M> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // Output left over from a failed write goes first; until it does,
+        // nothing more is accepted
+        self.write_output()?;
+        self.pending.extend_from_slice(buf);
+        self.process(false);
+        // `buf` is buffered either way, so an error here waits for the next call
+        let _ = self.write_output();
+        Ok(buf.len())
+    }
+
+    /// Write out held-back clusters and flush the underlying writer
+    ///
+    /// A combining mark written after a flush is not joined to the cluster
+    /// before it.
+    fn flush(&mut self) -> io::Result<()> {
+        self.process(true);
+        self.write_output()?;
+        self.get_mut().flush()
+    }
+}
+
+impl<W: Write, M: CharMap> Drop for StyleWriter<W, M> {
+    fn drop(&mut self) {
+        if self.inner.is_some() {
+            let _ = self.finish_pending();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style;
+
+    /// Feed `input` one byte at a time
+    fn bytewise<M: CharMap>(input: &[u8], map: M, ansi: bool) -> Vec<u8> {
+        let mut writer = StyleWriter::new(Vec::new(), map).preserve_ansi(ansi);
+        for byte in input {
+            writer.write_all(std::slice::from_ref(byte)).unwrap();
+        }
+        writer.finish().unwrap()
+    }
+
+    #[test]
+    fn test_bytewise_matches_style() {
+        let text = "Ünïcode e\u{0301} 👍🏽 🇺🇸 日本\nline two";
+        for map in [
+            Style::MathBold,
+            Style::Strikethrough,
+            Style::RegionalIndicator,
+            Style::Circled,
+        ] {
+            let out = bytewise(text.as_bytes(), map, false);
+            assert_eq!(String::from_utf8(out).unwrap(), style(text, map), "{map}");
+        }
+    }
+
+    #[test]
+    fn test_reversed_styles_work_per_line() {
+        let out = bytewise(b"abc\nde", Style::Inverted, false);
+        assert_eq!(String::from_utf8(out).unwrap(), "ɔqɐ\nǝp");
+    }
+
+    #[test]
+    fn test_ansi_preserved() {
+        let input = "\x1b[1;31mred\x1b[0m \x1b]8;;https://x.io\x07link\x1b]8;;\x1b\\";
+        let out = String::from_utf8(bytewise(input.as_bytes(), Style::Monospace, true)).unwrap();
+        assert_eq!(
+            out,
+            "\x1b[1;31m𝚛𝚎𝚍\x1b[0m \x1b]8;;https://x.io\x07𝚕𝚒𝚗𝚔\x1b]8;;\x1b\\"
+        );
+        // Without preservation, escape letters are styled too
+        let out = String::from_utf8(bytewise(b"\x1b[1m", Style::Monospace, false)).unwrap();
+        assert_eq!(out, "\x1b[𝟷𝚖");
+    }
+
+    #[test]
+    fn test_invalid_utf8_passes_through() {
+        let out = bytewise(b"a\xFFb\xE2\x82", Style::MathBold, false);
+        let expected = ["𝐚".as_bytes(), b"\xFF", "𝐛".as_bytes(), b"\xE2\x82"].concat();
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn test_pending_is_bounded() {
+        let mut writer = StyleWriter::new(Vec::new(), Style::Inverted);
+        for _ in 0..10_000 {
+            writer.write_all(b"a").unwrap();
+            assert!(writer.pending.len() <= MAX_PENDING);
+        }
+        assert_eq!(writer.finish().unwrap(), "ɐ".repeat(10_000).into_bytes());
+
+        // An unterminated OSC is styled as text once it is too long
+        let mut writer = StyleWriter::new(Vec::new(), Style::Fullwidth).preserve_ansi(true);
+        writer.write_all(b"\x1b]8;;").unwrap();
+        for _ in 0..2_000 {
+            writer.write_all(b"a").unwrap();
+        }
+        assert!(writer.pending.len() < MAX_ESCAPE);
+        let out = String::from_utf8(writer.finish().unwrap()).unwrap();
+        assert_eq!(out, format!("\x1b］８；；{}", "ａ".repeat(2_000)));
+    }
+
+    #[test]
+    fn test_large_chunks_keep_escapes_and_clusters() {
+        let text = "x".repeat(5000);
+        let mut writer = StyleWriter::new(Vec::new(), Style::Monospace).preserve_ansi(true);
+        writer
+            .write_all(format!("{text}\x1b[3").as_bytes())
+            .unwrap();
+        writer.write_all(b"1mX\x1b[0m").unwrap();
+        let out = String::from_utf8(writer.finish().unwrap()).unwrap();
+        assert_eq!(out, style(&text, Style::Monospace) + "\x1b[31m𝚇\x1b[0m");
+
+        let mut writer = StyleWriter::new(Vec::new(), Style::MathBold);
+        writer.write_all(format!("{text}e").as_bytes()).unwrap();
+        writer.write_all("\u{0301}".repeat(2).as_bytes()).unwrap();
+        let out = String::from_utf8(writer.finish().unwrap()).unwrap();
+        let expected = format!("{text}e\u{0301}\u{0301}");
+        assert_eq!(out, style(&expected, Style::MathBold));
+    }
+
+    /// Fails every other write, accepting at most two bytes at a time
+    struct Flaky {
+        out: Vec<u8>,
+        fail: bool,
+    }
+
+    impl Write for Flaky {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.fail = !self.fail;
+            if self.fail {
+                return Err(io::Error::new(io::ErrorKind::Other, "flaky"));
+            }
+            let n = buf.len().min(2);
+            self.out.extend_from_slice(&buf[..n]);
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_output_kept_on_error() {
+        let inner = Flaky {
+            out: Vec::new(),
+            fail: false,
+        };
+        let mut writer = StyleWriter::new(inner, Style::MathBold);
+        let mut input: &[u8] = b"retry me";
+        while !input.is_empty() {
+            if let Ok(n) = writer.write(input) {
+                input = &input[n..];
+            }
+        }
+        while writer.flush().is_err() {}
+        let out = std::mem::take(&mut writer.get_mut().out);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            style("retry me", Style::MathBold)
+        );
+    }
+
+    #[test]
+    fn test_drop_flushes() {
+        let mut out = Vec::new();
+        {
+            let mut writer = StyleWriter::new(&mut out, Style::Fullwidth);
+            writer.write_all(b"hi").unwrap();
+        }
+        assert_eq!(String::from_utf8(out).unwrap(), "ｈｉ");
+    }
+}
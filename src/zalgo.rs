@@ -0,0 +1,183 @@
+use crate::grapheme::{graphemes, is_emoji_component};
+
+/// How many combining marks [`zalgo`] stacks on each grapheme cluster
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ZalgoIntensity {
+    /// Up to 2 marks above and 2 below
+    Mild,
+
+    /// Up to 5 above, 1 through the middle and 5 below
+    Moderate,
+
+    /// Up to 10 above, 2 through the middle and 10 below
+    Heavy,
+}
+
+impl ZalgoIntensity {
+    /// Maximum marks above, through the middle and below each cluster
+    const fn limits(self) -> (u64, u64, u64) {
+        match self {
+            ZalgoIntensity::Mild => (2, 0, 2),
+            ZalgoIntensity::Moderate => (5, 1, 5),
+            ZalgoIntensity::Heavy => (10, 2, 10),
+        }
+    }
+}
+
+/// Combining marks drawn above the base character
+const ABOVE: &[char] = &[
+    '\u{0300}', '\u{0301}', '\u{0302}', '\u{0303}', '\u{0304}', '\u{0305}', '\u{0306}', '\u{0307}',
+    '\u{0308}', '\u{0309}', '\u{030A}', '\u{030B}', '\u{030C}', '\u{030D}', '\u{030E}', '\u{030F}',
+    '\u{0310}', '\u{0311}', '\u{0312}', '\u{0313}', '\u{0314}', '\u{031A}', '\u{033D}', '\u{033E}',
+    '\u{033F}', '\u{0342}', '\u{0343}', '\u{0344}', '\u{0346}', '\u{034A}', '\u{034B}', '\u{034C}',
+    '\u{0350}', '\u{0351}', '\u{0352}', '\u{0357}', '\u{035B}', '\u{0363}', '\u{0364}', '\u{0365}',
+    '\u{0366}', '\u{0367}', '\u{0368}', '\u{0369}', '\u{036A}', '\u{036B}', '\u{036C}', '\u{036D}',
+    '\u{036E}', '\u{036F}',
+];
+
+/// Combining marks drawn through or beside the base character
+const MIDDLE: &[char] = &[
+    '\u{0315}', '\u{031B}', '\u{0334}', '\u{0335}', '\u{0336}', '\u{0337}', '\u{0338}', '\u{0358}',
+    '\u{035C}', '\u{035D}', '\u{035E}', '\u{035F}', '\u{0360}', '\u{0361}', '\u{0362}',
+];
+
+/// Combining marks drawn below the base character
+const BELOW: &[char] = &[
+    '\u{0316}', '\u{0317}', '\u{0318}', '\u{0319}', '\u{031C}', '\u{031D}', '\u{031E}', '\u{031F}',
+    '\u{0320}', '\u{0321}', '\u{0322}', '\u{0323}', '\u{0324}', '\u{0325}', '\u{0326}', '\u{0327}',
+    '\u{0328}', '\u{0329}', '\u{032A}', '\u{032B}', '\u{032C}', '\u{032D}', '\u{032E}', '\u{032F}',
+    '\u{0330}', '\u{0331}', '\u{0332}', '\u{0333}', '\u{0339}', '\u{033A}', '\u{033B}', '\u{033C}',
+    '\u{0345}', '\u{0347}', '\u{0348}', '\u{0349}', '\u{034D}', '\u{034E}', '\u{0353}', '\u{0354}',
+    '\u{0355}', '\u{0356}', '\u{0359}', '\u{035A}',
+];
+
+/// SplitMix64, a small generator with a stable output sequence for each seed
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A value in `0..n`; the slight modulo bias is irrelevant here
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    fn push_marks(&mut self, marks: &[char], max: u64, out: &mut String) {
+        for _ in 0..self.below(max + 1) {
+            out.push(marks[self.below(marks.len() as u64) as usize]);
+        }
+    }
+}
+
+/// Stack random combining marks on every grapheme cluster ("Zalgo" text)
+///
+/// The same text, intensity and seed always produce the same output, on every
+/// platform. Marks from U+0300–U+036F go at the end of each cluster, so
+/// clusters stay whole and the result can be styled further; whitespace,
+/// control characters and emoji sequences are left alone.
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::{dezalgo, graphemes, zalgo, ZalgoIntensity};
+///
+/// let glitched = zalgo("hello", ZalgoIntensity::Heavy, 42);
+/// assert_eq!(glitched, zalgo("hello", ZalgoIntensity::Heavy, 42));
+/// assert_eq!(graphemes(&glitched).count(), 5);
+/// assert_eq!(dezalgo(&glitched), "hello");
+/// ```
+pub fn zalgo(text: &str, intensity: ZalgoIntensity, seed: u64) -> String {
+    let (above, middle, below) = intensity.limits();
+    let mut rng = SplitMix64(seed);
+    let mut out = String::with_capacity(text.len() * 8);
+    for cluster in graphemes(text) {
+        out.push_str(cluster);
+        let skip = cluster.chars().all(|c| c.is_whitespace() || c.is_control())
+            || cluster.chars().any(is_emoji_component);
+        if !skip {
+            rng.push_marks(ABOVE, above, &mut out);
+            rng.push_marks(MIDDLE, middle, &mut out);
+            rng.push_marks(BELOW, below, &mut out);
+        }
+    }
+    out
+}
+
+/// Remove every combining mark in U+0300–U+036F, undoing [`zalgo`]
+///
+/// Precomposed letters such as `é` are kept, but accents written as separate
+/// combining marks are removed too, as are the marks added by
+/// [`Style::Strikethrough`](crate::Style::Strikethrough) and
+/// [`Effect`](crate::Effect)s.
+///
+/// # Examples
+///
+/// ```
+/// use prettychars::dezalgo;
+///
+/// assert_eq!(dezalgo("c\u{0337}\u{031B}a\u{0353}fé"), "café");
+/// ```
+pub fn dezalgo(text: &str) -> String {
+    text.chars()
+        .filter(|c| !('\u{0300}'..='\u{036F}').contains(c))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{style, Style};
+
+    #[test]
+    fn test_seeds_are_stable() {
+        let mild = zalgo("ab", ZalgoIntensity::Mild, 7);
+        assert_eq!(mild, zalgo("ab", ZalgoIntensity::Mild, 7));
+        assert_ne!(
+            zalgo("glitch", ZalgoIntensity::Heavy, 1),
+            zalgo("glitch", ZalgoIntensity::Heavy, 2)
+        );
+        // Pin the sequence so generator changes are caught
+        let mut rng = SplitMix64(0);
+        assert_eq!(rng.next(), 0xE220_A839_7B1D_CDAF);
+    }
+
+    #[test]
+    fn test_mark_limits() {
+        let text = "abcdefghijklmnopqrstuvwxyz".repeat(4);
+        for (intensity, max) in [
+            (ZalgoIntensity::Mild, 4),
+            (ZalgoIntensity::Moderate, 11),
+            (ZalgoIntensity::Heavy, 22),
+        ] {
+            let out = zalgo(&text, intensity, 99);
+            let counts: Vec<usize> = graphemes(&out).map(|g| g.chars().count() - 1).collect();
+            assert_eq!(counts.len(), text.len());
+            assert!(counts.iter().all(|&n| n <= max));
+            assert!(counts.iter().any(|&n| n > 0));
+        }
+    }
+
+    #[test]
+    fn test_skips_whitespace_and_emoji() {
+        let out = zalgo("a 👍🏽\n", ZalgoIntensity::Heavy, 3);
+        assert!(out.ends_with(" 👍🏽\n"));
+        assert_eq!(dezalgo(&out), "a 👍🏽\n");
+    }
+
+    #[test]
+    fn test_composes_with_styles() {
+        let bold = style("Zalgo", Style::MathBold);
+        let glitched = zalgo("Zalgo", ZalgoIntensity::Moderate, 5);
+        assert_eq!(
+            style(&glitched, Style::MathBold),
+            zalgo(&bold, ZalgoIntensity::Moderate, 5)
+        );
+        assert_eq!(dezalgo(&style(&glitched, Style::MathBold)), bold);
+    }
+}